@@ -0,0 +1,44 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use k_edit_distance::{k_edit_distance, k_edit_distance_breakdown};
+
+const WORDS: &[&str] = &[
+    "국어",
+    "숙어",
+    "나무가지",
+    "신문",
+    "검은색",
+    "분홍색",
+    "진공청소기",
+    "택시",
+    "모시금자라남생이잎벌레",
+];
+
+fn bench_k_edit_distance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("k_edit_distance");
+    group.bench_function("words", |b| {
+        b.iter(|| {
+            for outer in WORDS {
+                for inner in WORDS {
+                    black_box(k_edit_distance(black_box(inner), black_box(outer)));
+                }
+            }
+        })
+    });
+    // The breakdown goes through the full syllable matrix with a Vec for every syllable, the
+    // same score the slower way
+    group.bench_function("words_breakdown", |b| {
+        b.iter(|| {
+            for outer in WORDS {
+                for inner in WORDS {
+                    black_box(k_edit_distance_breakdown(black_box(inner), black_box(outer)).score);
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_k_edit_distance);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+/* C API for k-edit-distance, build the library to link against with
+ * `cargo rustc --release --lib --features ffi --crate-type staticlib` (or `cdylib` for a shared
+ * library).
+ *
+ * Strings are NUL-terminated UTF-8. A null pointer or invalid UTF-8 returns SIZE_MAX from the
+ * integer functions and NaN from the float ones. */
+
+#ifndef K_EDIT_DISTANCE_H
+#define K_EDIT_DISTANCE_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* levenshtein_distance of s and t */
+size_t ke_levenshtein(const char *s, const char *t);
+
+/* k_edit_distance of s and t, from 0.0 for the same to 1.0 for most different */
+float ke_k_edit_distance(const char *s, const char *t);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
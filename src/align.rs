@@ -0,0 +1,284 @@
+//! Sequence alignments that maximize a score, the way bioinformatics does it, instead of
+//! minimizing an edit count.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// One `(s, t)` pair per column of an alignment, `None` is a gap.
+pub type Alignment = Vec<(Option<char>, Option<char>)>;
+
+/// Global alignment of the whole of `s` against the whole of `t` with Needleman-Wunsch. Each
+/// column adds `match_score` when the characters are the same, `mismatch` when they differ and
+/// `gap` when one side is a gap, so `mismatch` and `gap` are usually negative.
+///
+/// Returns the best total score and the alignment, one `(s, t)` pair per column with `None` for
+/// a gap. When several alignments score the same, lining characters up is preferred over a gap
+/// in `t`, which is preferred over a gap in `s`.
+///
+/// # Examples
+///
+/// ```
+/// let s: Vec<char> = "GATTACA".chars().collect();
+/// let t: Vec<char> = "GCATGCU".chars().collect();
+/// let (score, alignment) = k_edit_distance::needleman_wunsch(&s, &t, 1, -1, -1);
+/// assert_eq!(score, 0);
+/// assert_eq!(alignment.len(), 8);
+/// ```
+pub fn needleman_wunsch(
+    s: &[char],
+    t: &[char],
+    match_score: i32,
+    mismatch: i32,
+    gap: i32,
+) -> (i32, Alignment) {
+    let m = s.len();
+    let n = t.len();
+    let score = |a: char, b: char| if a == b { match_score } else { mismatch };
+    let mut d = vec![vec![0; n + 1]; m + 1];
+
+    for i in 1..=m {
+        d[i][0] = d[i - 1][0] + gap;
+    }
+
+    for j in 1..=n {
+        d[0][j] = d[0][j - 1] + gap;
+    }
+
+    for i in 1..=m {
+        for j in 1..=n {
+            d[i][j] = (d[i - 1][j - 1] + score(s[i - 1], t[j - 1]))
+                .max(d[i - 1][j] + gap)
+                .max(d[i][j - 1] + gap);
+        }
+    }
+
+    let mut alignment = vec![];
+    let (mut i, mut j) = (m, n);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + score(s[i - 1], t[j - 1]) {
+            alignment.push((Some(s[i - 1]), Some(t[j - 1])));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + gap {
+            alignment.push((Some(s[i - 1]), None));
+            i -= 1;
+        } else {
+            alignment.push((None, Some(t[j - 1])));
+            j -= 1;
+        }
+    }
+    alignment.reverse();
+
+    (d[m][n], alignment)
+}
+
+/// Local alignment with Smith-Waterman, the best scoring pair of substrings of `s` and `t`
+/// rather than the whole of both. Scoring is the same as `needleman_wunsch` except a score never
+/// drops below 0, so a bad stretch just starts a new alignment.
+///
+/// Returns the best score and where the best segment ends as `(score, s_end, t_end)`, the ends
+/// being exclusive `char` indices. If several segments score the same the first one found
+/// (lowest `s_end`, then lowest `t_end`) wins. Nothing aligning at all gives `(0, 0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// let s: Vec<char> = "xxHELLOxx".chars().collect();
+/// let t: Vec<char> = "yyyHELLOyyy".chars().collect();
+/// assert_eq!(k_edit_distance::smith_waterman(&s, &t, 2, -1, -1), (10, 7, 8));
+/// ```
+pub fn smith_waterman(
+    s: &[char],
+    t: &[char],
+    match_score: i32,
+    mismatch: i32,
+    gap: i32,
+) -> (i32, usize, usize) {
+    let n = t.len();
+    let mut prev = vec![0; n + 1];
+    let mut curr = vec![0; n + 1];
+    let mut best = (0, 0, 0);
+
+    for i in 1..=s.len() {
+        for j in 1..=n {
+            let score = if s[i - 1] == t[j - 1] {
+                match_score
+            } else {
+                mismatch
+            };
+
+            curr[j] = (prev[j - 1] + score)
+                .max(prev[j] + gap)
+                .max(curr[j - 1] + gap)
+                .max(0);
+            if curr[j] > best.0 {
+                best = (curr[j], i, j);
+            }
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    best
+}
+
+/// Global alignment score like `needleman_wunsch` but with affine gaps (Gotoh), a run of `L`
+/// gaps in a row scores `gap_open + (L - 1) * gap_extend` instead of `L * gap`. Making
+/// `gap_extend` cheaper than `gap_open` favours one long gap, like a dropped word, over lots of
+/// short ones.
+///
+/// With `gap_open == gap_extend` this is the same score as `needleman_wunsch` with that `gap`.
+///
+/// # Examples
+///
+/// ```
+/// let s: Vec<char> = "ACGTACGT".chars().collect();
+/// let t: Vec<char> = "ACGT".chars().collect();
+/// assert_eq!(k_edit_distance::gotoh_align(&s, &t, 1, -1, -5, -1), -4);
+/// assert_eq!(k_edit_distance::gotoh_align(&s, &t, 1, -1, -5, -5), -16);
+/// ```
+pub fn gotoh_align(
+    s: &[char],
+    t: &[char],
+    match_score: i32,
+    mismatch: i32,
+    gap_open: i32,
+    gap_extend: i32,
+) -> i32 {
+    // Low enough to never win but with room to add penalties without overflowing
+    const NONE: i32 = i32::MIN / 2;
+    let m = s.len();
+    let n = t.len();
+    let score = |a: char, b: char| if a == b { match_score } else { mismatch };
+
+    // The best score of s[..i] against t[..j] ending with s[i - 1] lined up with t[j - 1]...
+    let mut lined_up = vec![vec![NONE; n + 1]; m + 1];
+    // ...with s[i - 1] against a gap...
+    let mut gap_in_t = vec![vec![NONE; n + 1]; m + 1];
+    // ...or with a gap against t[j - 1]
+    let mut gap_in_s = vec![vec![NONE; n + 1]; m + 1];
+
+    lined_up[0][0] = 0;
+    for (i, row) in gap_in_t.iter_mut().enumerate().skip(1) {
+        row[0] = gap_open + (i as i32 - 1) * gap_extend;
+    }
+    for (j, cell) in gap_in_s[0].iter_mut().enumerate().skip(1) {
+        *cell = gap_open + (j as i32 - 1) * gap_extend;
+    }
+
+    for i in 1..=m {
+        for j in 1..=n {
+            lined_up[i][j] = lined_up[i - 1][j - 1]
+                .max(gap_in_t[i - 1][j - 1])
+                .max(gap_in_s[i - 1][j - 1])
+                + score(s[i - 1], t[j - 1]);
+            gap_in_t[i][j] = (lined_up[i - 1][j] + gap_open)
+                .max(gap_in_t[i - 1][j] + gap_extend)
+                .max(gap_in_s[i - 1][j] + gap_open);
+            gap_in_s[i][j] = (lined_up[i][j - 1] + gap_open)
+                .max(gap_in_s[i][j - 1] + gap_extend)
+                .max(gap_in_t[i][j - 1] + gap_open);
+        }
+    }
+
+    lined_up[m][n].max(gap_in_t[m][n]).max(gap_in_s[m][n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn test_needleman_wunsch() {
+        let (score, alignment) = needleman_wunsch(&chars("GATTACA"), &chars("GCATGCU"), 1, -1, -1);
+        assert_eq!(score, 0);
+        // Removing the gaps gives back both inputs
+        let s: Vec<char> = alignment.iter().filter_map(|(a, _)| *a).collect();
+        let t: Vec<char> = alignment.iter().filter_map(|(_, b)| *b).collect();
+        assert_eq!(s, chars("GATTACA"));
+        assert_eq!(t, chars("GCATGCU"));
+        // The alignment really does have that score
+        let rescored: i32 = alignment
+            .iter()
+            .map(|pair| match pair {
+                (Some(a), Some(b)) if a == b => 1,
+                _ => -1,
+            })
+            .sum();
+        assert_eq!(rescored, score);
+
+        let (score, alignment) = needleman_wunsch(&chars("AC"), &chars("ABC"), 2, -1, -2);
+        assert_eq!(score, 2);
+        assert_eq!(
+            alignment,
+            vec![
+                (Some('A'), Some('A')),
+                (None, Some('B')),
+                (Some('C'), Some('C'))
+            ]
+        );
+
+        assert_eq!(needleman_wunsch(&[], &[], 1, -1, -1), (0, vec![]));
+        assert_eq!(
+            needleman_wunsch(&chars("ab"), &[], 1, -1, -1),
+            (-2, vec![(Some('a'), None), (Some('b'), None)])
+        );
+        // With unit penalties and no reward it is minus the Levenshtein distance
+        let (score, _) = needleman_wunsch(&chars("kitten"), &chars("sitting"), 0, -1, -1);
+        assert_eq!(score, -3);
+    }
+
+    #[test]
+    fn test_smith_waterman() {
+        let s = chars("xxxxHELLOxxxx");
+        let t = chars("yyHELLOyyyyyyy");
+        let (score, s_end, t_end) = smith_waterman(&s, &t, 2, -1, -1);
+        assert_eq!((score, s_end, t_end), (10, 9, 7));
+        assert_eq!(&s[s_end - 5..s_end], &t[t_end - 5..t_end]);
+        // Forcing the whole strings to line up does a lot worse
+        let (global, _) = needleman_wunsch(&s, &t, 2, -1, -1);
+        assert!(score > global);
+
+        // Wikipedia's example
+        let (score, _, _) = smith_waterman(&chars("TGTTACGG"), &chars("GGTTGACTA"), 3, -3, -2);
+        assert_eq!(score, 13);
+
+        assert_eq!(
+            smith_waterman(&chars("abc"), &chars("xyz"), 1, -1, -1),
+            (0, 0, 0)
+        );
+        assert_eq!(smith_waterman(&[], &chars("xyz"), 1, -1, -1), (0, 0, 0));
+        // The first of two equally good segments
+        assert_eq!(
+            smith_waterman(&chars("abab"), &chars("ab"), 1, -1, -1),
+            (2, 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_gotoh_align() {
+        let s = chars("ACGTACGT");
+        let t = chars("ACGT");
+        // One gap of 4 is -5 - 3 when extending is cheap...
+        assert_eq!(gotoh_align(&s, &t, 1, -1, -5, -1), -4);
+        // ...but -20 when it costs as much as opening
+        assert_eq!(gotoh_align(&s, &t, 1, -1, -5, -5), -16);
+        assert!(gotoh_align(&s, &t, 1, -1, -5, -1) > gotoh_align(&s, &t, 1, -1, -5, -5));
+
+        // A dropped word
+        let s = chars("the quick brown fox");
+        let t = chars("the fox");
+        assert_eq!(gotoh_align(&s, &t, 1, -1, -3, 0), 4);
+
+        for (s, t) in [("GATTACA", "GCATGCU"), ("AC", "ABC"), ("", "abc"), ("", "")] {
+            let (s, t) = (chars(s), chars(t));
+            assert_eq!(
+                gotoh_align(&s, &t, 2, -1, -2, -2),
+                needleman_wunsch(&s, &t, 2, -1, -2).0
+            );
+        }
+    }
+}
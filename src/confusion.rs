@@ -0,0 +1,109 @@
+//! Distances for OCR output where some glyphs are easy to mistake for each other.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::fractional_levenshtein;
+
+/// How much substituting one character for another costs, from 0.0 for "can't tell them apart"
+/// to 1.0 for a normal substitution. Pairs work in both directions and any pair that isn't in
+/// the matrix costs 1.0.
+///
+/// # Examples
+///
+/// ```
+/// let mut costs = k_edit_distance::ConfusionMatrix::new();
+/// costs.insert('0', 'O', 0.1);
+/// assert_eq!(costs.cost('O', '0'), 0.1);
+/// assert_eq!(costs.cost('O', 'Q'), 1.0);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfusionMatrix {
+    costs: BTreeMap<(char, char), f32>,
+}
+
+impl ConfusionMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cost of substituting `a` for `b` (and `b` for `a`), clamped to 0.0..=1.0.
+    pub fn insert(&mut self, a: char, b: char, cost: f32) {
+        self.costs
+            .insert((a.min(b), a.max(b)), cost.clamp(0.0, 1.0));
+    }
+
+    /// The cost of substituting `a` for `b`, 0.0 if they're the same
+    pub fn cost(&self, a: char, b: char) -> f32 {
+        if a == b {
+            return 0.0;
+        }
+        self.costs
+            .get(&(a.min(b), a.max(b)))
+            .copied()
+            .unwrap_or(1.0)
+    }
+}
+
+impl FromIterator<(char, char, f32)> for ConfusionMatrix {
+    fn from_iter<I: IntoIterator<Item = (char, char, f32)>>(iter: I) -> Self {
+        let mut matrix = Self::new();
+        for (a, b, cost) in iter {
+            matrix.insert(a, b, cost);
+        }
+        matrix
+    }
+}
+
+/// Levenshtein distance where substitutions cost what `costs` says, inserting and deleting cost
+/// 1.0. Only one character for one character confusions are handled so "rn" for "m" is still
+/// a substitution and a deletion.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{ConfusionMatrix, confusion_weighted_distance};
+///
+/// let costs: ConfusionMatrix = [('0', 'O', 0.1), ('1', 'l', 0.2)].into_iter().collect();
+/// assert_eq!(confusion_weighted_distance("0CR", "OCR", &costs), 0.1);
+/// assert_eq!(confusion_weighted_distance("XCR", "OCR", &costs), 1.0);
+/// ```
+pub fn confusion_weighted_distance(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    costs: &ConfusionMatrix,
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+    fractional_levenshtein(&s, &t, |a, b| costs.cost(*a, *b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confusion_weighted_distance() {
+        let costs: ConfusionMatrix = [('0', 'O', 0.1), ('1', 'l', 0.2), ('5', 'S', 0.3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(confusion_weighted_distance("0CR", "OCR", &costs), 0.1);
+        assert!(
+            confusion_weighted_distance("0CR", "OCR", &costs)
+                < confusion_weighted_distance("0CR", "OCR", &ConfusionMatrix::new())
+        );
+        assert_eq!(confusion_weighted_distance("OCR", "0CR", &costs), 0.1);
+        assert_eq!(confusion_weighted_distance("he11o", "hello", &costs), 0.4);
+        assert_eq!(confusion_weighted_distance("5ample", "Sample", &costs), 0.3);
+        assert_eq!(confusion_weighted_distance("OCR", "OCR", &costs), 0.0);
+        assert_eq!(confusion_weighted_distance("OCR", "", &costs), 3.0);
+
+        let mut clamped = ConfusionMatrix::new();
+        clamped.insert('a', 'b', 5.0);
+        clamped.insert('c', 'd', -1.0);
+        assert_eq!(clamped.cost('a', 'b'), 1.0);
+        assert_eq!(clamped.cost('d', 'c'), 0.0);
+    }
+}
@@ -0,0 +1,190 @@
+//! Rendering the edits between two strings for people to read.
+
+use alloc::string::String;
+
+use crate::{EditOp, levenshtein_ops};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// The characters of `s` and `t` merged into one string by `levenshtein_ops`, with what was
+/// deleted from `s` in red and what was inserted from `t` in green using ANSI escape codes.
+/// A substitution shows up as a deletion followed by an insertion, and runs of edits next to each
+/// other are grouped so the colors don't flicker between every character.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(
+///     k_edit_distance::colored_diff("cat", "cut"),
+///     "c\x1b[31ma\x1b[0m\x1b[32mu\x1b[0mt"
+/// );
+/// ```
+pub fn colored_diff(s: impl AsRef<str>, t: impl AsRef<str>) -> String {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    render_diff(s, t, [RED, RESET], [GREEN, RESET])
+}
+
+/// Like `colored_diff` for terminals without color, deletions are wrapped in `[-` `-]` and
+/// insertions in `{+` `+}` like `wdiff` does.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::colored_diff_no_ansi("cat", "cut"), "c[-a-]{+u+}t");
+/// assert_eq!(k_edit_distance::colored_diff_no_ansi("cat", "cats"), "cat{+s+}");
+/// ```
+pub fn colored_diff_no_ansi(s: impl AsRef<str>, t: impl AsRef<str>) -> String {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    render_diff(s, t, ["[-", "-]"], ["{+", "+}"])
+}
+
+/// The classic three line view of how `s` lines up with `t`: `s` on top, `t` underneath and a
+/// `|` between them wherever they match, with `-` filling in for characters only the other
+/// string has. The lines are joined with `\n` and come from the same `levenshtein_ops` as
+/// `colored_diff`.
+///
+/// Every line has one `char` per column, so it only lines up in a monospace font where every
+/// character is the same width. Wide characters like Hangul take up two columns in most
+/// terminals and will push the rest of their line over.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(
+///     k_edit_distance::format_alignment("kitten", "sitting"),
+///     "kitten-\n ||| | \nsitting"
+/// );
+/// ```
+pub fn format_alignment(s: impl AsRef<str>, t: impl AsRef<str>) -> String {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let (mut top, mut middle, mut bottom) = (String::new(), String::new(), String::new());
+    for op in levenshtein_ops(s, t) {
+        let (s_char, bar, t_char) = match op {
+            EditOp::Match(c) => (c, '|', c),
+            EditOp::Substitute(a, b) => (a, ' ', b),
+            EditOp::Delete(c) => (c, ' ', '-'),
+            EditOp::Insert(c) => ('-', ' ', c),
+        };
+        top.push(s_char);
+        middle.push(bar);
+        bottom.push(t_char);
+    }
+
+    [top, middle, bottom].join("\n")
+}
+
+fn render_diff(s: &str, t: &str, deleted: [&str; 2], inserted: [&str; 2]) -> String {
+    let mut rendered = String::new();
+    let (mut deletions, mut insertions) = (String::new(), String::new());
+    for op in levenshtein_ops(s, t) {
+        match op {
+            EditOp::Match(c) => {
+                flush(&mut rendered, &mut deletions, deleted);
+                flush(&mut rendered, &mut insertions, inserted);
+                rendered.push(c);
+            }
+            EditOp::Substitute(a, b) => {
+                deletions.push(a);
+                insertions.push(b);
+            }
+            EditOp::Delete(c) => deletions.push(c),
+            EditOp::Insert(c) => insertions.push(c),
+        }
+    }
+    flush(&mut rendered, &mut deletions, deleted);
+    flush(&mut rendered, &mut insertions, inserted);
+
+    rendered
+}
+
+fn flush(rendered: &mut String, run: &mut String, [open, close]: [&str; 2]) {
+    if run.is_empty() {
+        return;
+    }
+    rendered.push_str(open);
+    rendered.push_str(run);
+    rendered.push_str(close);
+    run.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Splits a `colored_diff` back into what came from `s` and what came from `t`
+    fn split_ansi(diff: &str) -> (String, String) {
+        let (mut s, mut t) = (String::new(), String::new());
+        let mut color = RESET;
+        let mut rest = diff;
+        while let Some(c) = rest.chars().next() {
+            if let Some(code) = [RED, GREEN, RESET]
+                .into_iter()
+                .find(|c| rest.starts_with(c))
+            {
+                color = code;
+                rest = &rest[code.len()..];
+                continue;
+            }
+            if color != GREEN {
+                s.push(c);
+            }
+            if color != RED {
+                t.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+
+        (s, t)
+    }
+
+    #[test]
+    fn test_colored_diff() {
+        for (s, t) in [
+            ("cat", "cut"),
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("", ""),
+            ("국어", "국화"),
+            ("flaw", "lawn"),
+        ] {
+            assert_eq!(split_ansi(&colored_diff(s, t)), (s.into(), t.into()));
+        }
+
+        assert_eq!(colored_diff("same", "same"), "same");
+        assert_eq!(colored_diff("", "ab"), "\x1b[32mab\x1b[0m");
+    }
+
+    #[test]
+    fn test_colored_diff_no_ansi() {
+        assert_eq!(
+            colored_diff_no_ansi("kitten", "sitting"),
+            "[-k-]{+s+}itt[-e-]{+i+}n{+g+}"
+        );
+        assert_eq!(colored_diff_no_ansi("abc", ""), "[-abc-]");
+        assert_eq!(colored_diff_no_ansi("", ""), "");
+    }
+
+    #[test]
+    fn test_format_alignment() {
+        let alignment = format_alignment("kitten", "sitting");
+        let lines: Vec<&str> = alignment.lines().collect();
+        assert_eq!(lines, ["kitten-", " ||| | ", "sitting"]);
+
+        assert_eq!(format_alignment("flaw", "lawn"), "flaw-\n ||| \n-lawn");
+        assert_eq!(format_alignment("국어", "국화어"), "국-어\n| |\n국화어");
+        assert_eq!(format_alignment("same", "same"), "same\n||||\nsame");
+        assert_eq!(format_alignment("", ""), "\n\n");
+
+        // Every line has a char for each column
+        for (s, t) in [("kitten", "sitting"), ("국어사전", "국어"), ("", "abc")] {
+            let alignment = format_alignment(s, t);
+            let widths: Vec<usize> = alignment.split('\n').map(|l| l.chars().count()).collect();
+            assert_eq!(widths[0], widths[1]);
+            assert_eq!(widths[1], widths[2]);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! A C API, see `include/k_edit_distance.h` for the declarations.
+//!
+//! Strings are NUL-terminated UTF-8. A null pointer or invalid UTF-8 gives back a sentinel
+//! instead of a distance, `usize::MAX` (`SIZE_MAX`) for integers and NaN for floats.
+
+use core::ffi::{CStr, c_char};
+
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string that stays valid for `'a`.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    // SAFETY: checked for null above, the caller promises the rest
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+/// `levenshtein_distance` between two C strings, `SIZE_MAX` if either is null or not UTF-8.
+///
+/// # Safety
+///
+/// `s` and `t` must each be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ke_levenshtein(s: *const c_char, t: *const c_char) -> usize {
+    // SAFETY: the caller promises both are null or NUL-terminated
+    match unsafe { (to_str(s), to_str(t)) } {
+        (Some(s), Some(t)) => crate::levenshtein_distance(s, t),
+        _ => usize::MAX,
+    }
+}
+
+/// `k_edit_distance` between two C strings, NaN if either is null or not UTF-8.
+///
+/// # Safety
+///
+/// `s` and `t` must each be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ke_k_edit_distance(s: *const c_char, t: *const c_char) -> f32 {
+    // SAFETY: the caller promises both are null or NUL-terminated
+    match unsafe { (to_str(s), to_str(t)) } {
+        (Some(s), Some(t)) => crate::k_edit_distance(s, t),
+        _ => f32::NAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use core::ptr;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_ffi() {
+        let s = CString::new("국어").unwrap();
+        let t = CString::new("숙어").unwrap();
+        unsafe {
+            assert_eq!(ke_levenshtein(s.as_ptr(), t.as_ptr()), 1);
+            assert_eq!(
+                ke_k_edit_distance(s.as_ptr(), t.as_ptr()),
+                crate::k_edit_distance("국어", "숙어")
+            );
+
+            assert_eq!(ke_levenshtein(ptr::null(), t.as_ptr()), usize::MAX);
+            assert!(ke_k_edit_distance(s.as_ptr(), ptr::null()).is_nan());
+
+            let invalid = CString::new(vec![0xff, 0xfe]).unwrap();
+            assert_eq!(ke_levenshtein(invalid.as_ptr(), t.as_ptr()), usize::MAX);
+            assert!(ke_k_edit_distance(invalid.as_ptr(), t.as_ptr()).is_nan());
+        }
+    }
+}
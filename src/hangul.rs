@@ -0,0 +1,637 @@
+//! Hangul syllable arithmetic from the Unicode standard, see section 3.12 "Conjoining Jamo
+//! Behavior".
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use unicode_normalization::UnicodeNormalization;
+
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+/// Splits a precomposed Hangul syllable into its initial consonant (choseong), vowel (jungseong)
+/// and final consonant (jongseong) if it has one. The jamo are conjoining jamo (U+1100 to
+/// U+11FF), the same as NFD would give. Returns `None` for anything that isn't a Hangul
+/// syllable, including jamo on their own.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::decompose_syllable;
+///
+/// assert_eq!(
+///     decompose_syllable('각'),
+///     Some(('\u{1100}', '\u{1161}', Some('\u{11A8}')))
+/// );
+/// assert_eq!(decompose_syllable('가'), Some(('\u{1100}', '\u{1161}', None)));
+/// assert_eq!(decompose_syllable('a'), None);
+/// ```
+pub fn decompose_syllable(c: char) -> Option<(char, char, Option<char>)> {
+    let s_index = (c as u32).checked_sub(S_BASE).filter(|&i| i < S_COUNT)?;
+
+    let l = char::from_u32(L_BASE + s_index / N_COUNT)?;
+    let v = char::from_u32(V_BASE + (s_index % N_COUNT) / T_COUNT)?;
+    let t = match s_index % T_COUNT {
+        0 => None,
+        t_index => char::from_u32(T_BASE + t_index),
+    };
+
+    Some((l, v, t))
+}
+
+/// Joins conjoining jamo back into a precomposed Hangul syllable, the inverse of
+/// `decompose_syllable`. Returns `None` if `cho` isn't a leading consonant (U+1100 to U+1112),
+/// `jung` isn't a vowel (U+1161 to U+1175) or `jong` isn't a trailing consonant (U+11A8 to
+/// U+11C2).
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::compose_syllable;
+///
+/// assert_eq!(
+///     compose_syllable('\u{1100}', '\u{1161}', Some('\u{11A8}')),
+///     Some('각')
+/// );
+/// assert_eq!(compose_syllable('\u{1100}', '\u{1161}', None), Some('가'));
+/// assert_eq!(compose_syllable('a', '\u{1161}', None), None);
+/// ```
+pub fn compose_syllable(cho: char, jung: char, jong: Option<char>) -> Option<char> {
+    let l_index = (cho as u32).checked_sub(L_BASE).filter(|&i| i < L_COUNT)?;
+    let v_index = (jung as u32).checked_sub(V_BASE).filter(|&i| i < V_COUNT)?;
+    let t_index = match jong {
+        // T_BASE itself is the "no final consonant" slot so it isn't a valid jamo
+        Some(jong) => (jong as u32)
+            .checked_sub(T_BASE)
+            .filter(|&i| i > 0 && i < T_COUNT)?,
+        None => 0,
+    };
+
+    char::from_u32(S_BASE + l_index * N_COUNT + v_index * T_COUNT + t_index)
+}
+
+/// The leading consonants (choseong) as compatibility jamo, in the same order as U+1100 to
+/// U+1112.
+const CHOSEONG_COMPATIBILITY: [char; L_COUNT as usize] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ',
+    'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// Leading consonants as compatibility jamo, everything else as is
+fn choseong_compatibility(c: char) -> char {
+    match (c as u32).checked_sub(L_BASE) {
+        Some(l_index) if l_index < L_COUNT => CHOSEONG_COMPATIBILITY[l_index as usize],
+        _ => c,
+    }
+}
+
+/// The trailing consonants (jongseong) as compatibility jamo, in the same order as U+11A8 to
+/// U+11C2.
+const JONGSEONG_COMPATIBILITY: [char; T_COUNT as usize - 1] = [
+    'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ', 'ㄻ', 'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ',
+    'ㅂ', 'ㅄ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// Any conjoining jamo as the compatibility jamo a keyboard would type, everything else as is
+pub(crate) fn compatibility_jamo(c: char) -> char {
+    let code = c as u32;
+    if (V_BASE..V_BASE + V_COUNT).contains(&code) {
+        // The vowels are in the same order in both blocks
+        return char::from_u32(code - V_BASE + 'ㅏ' as u32).unwrap();
+    }
+    if (T_BASE + 1..T_BASE + T_COUNT).contains(&code) {
+        return JONGSEONG_COMPATIBILITY[(code - T_BASE - 1) as usize];
+    }
+    choseong_compatibility(c)
+}
+
+/// The other way around from `compatibility_jamo`, a compatibility jamo on its own as the
+/// conjoining jamo NFD gives for a syllable. A consonant has no position on its own so it's
+/// taken as leading, unless it can only be a final like ㄳ. Archaic jamo and everything else are
+/// left as is.
+pub(crate) fn conjoining_jamo(c: char) -> char {
+    if ('ㅏ'..='ㅣ').contains(&c) {
+        return char::from_u32(c as u32 - 'ㅏ' as u32 + V_BASE).unwrap();
+    }
+    leading_jamo(c).or_else(|| trailing_jamo(c)).unwrap_or(c)
+}
+
+/// Whether typing just the initial consonants (초성) in `query_chosung` matches the start of
+/// `word`, like "ㄱㅇ" for "국어". This is a prefix match so "ㅇ" doesn't match "국어".
+///
+/// Each Hangul syllable in `word` is replaced by its initial consonant and anything that isn't
+/// a Hangul syllable is compared as is. The query can use compatibility jamo (what a keyboard
+/// types) or conjoining leading consonants.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::matches_chosung;
+///
+/// assert!(matches_chosung("ㄱㅇ", "국어"));
+/// assert!(matches_chosung("ㄱ", "국어"));
+/// assert!(!matches_chosung("ㅇ", "국어"));
+/// assert!(matches_chosung("ㅋㅍa", "컴퓨ab"));
+/// ```
+pub fn matches_chosung(query_chosung: &str, word: &str) -> bool {
+    let mut word_chosung = word.chars().map(|c| match decompose_syllable(c) {
+        Some((cho, _, _)) => choseong_compatibility(cho),
+        None => choseong_compatibility(c),
+    });
+
+    query_chosung
+        .chars()
+        .map(choseong_compatibility)
+        .all(|q| word_chosung.next() == Some(q))
+}
+
+/// The double final consonants (겹받침) with the two consonants they're made of, as both
+/// conjoining trailing consonants and compatibility jamo.
+const DOUBLE_FINALS: [(char, char, char); 22] = [
+    ('\u{11AA}', '\u{11A8}', '\u{11BA}'),
+    ('\u{11AC}', '\u{11AB}', '\u{11BD}'),
+    ('\u{11AD}', '\u{11AB}', '\u{11C2}'),
+    ('\u{11B0}', '\u{11AF}', '\u{11A8}'),
+    ('\u{11B1}', '\u{11AF}', '\u{11B7}'),
+    ('\u{11B2}', '\u{11AF}', '\u{11B8}'),
+    ('\u{11B3}', '\u{11AF}', '\u{11BA}'),
+    ('\u{11B4}', '\u{11AF}', '\u{11C0}'),
+    ('\u{11B5}', '\u{11AF}', '\u{11C1}'),
+    ('\u{11B6}', '\u{11AF}', '\u{11C2}'),
+    ('\u{11B9}', '\u{11B8}', '\u{11BA}'),
+    ('ㄳ', 'ㄱ', 'ㅅ'),
+    ('ㄵ', 'ㄴ', 'ㅈ'),
+    ('ㄶ', 'ㄴ', 'ㅎ'),
+    ('ㄺ', 'ㄹ', 'ㄱ'),
+    ('ㄻ', 'ㄹ', 'ㅁ'),
+    ('ㄼ', 'ㄹ', 'ㅂ'),
+    ('ㄽ', 'ㄹ', 'ㅅ'),
+    ('ㄾ', 'ㄹ', 'ㅌ'),
+    ('ㄿ', 'ㄹ', 'ㅍ'),
+    ('ㅀ', 'ㄹ', 'ㅎ'),
+    ('ㅄ', 'ㅂ', 'ㅅ'),
+];
+
+/// The two consonants a double final consonant like ㄺ is made of, `None` for anything else
+pub(crate) fn split_double_final(c: char) -> Option<(char, char)> {
+    DOUBLE_FINALS
+        .iter()
+        .find(|(double, _, _)| *double == c)
+        .map(|(_, first, second)| (*first, *second))
+}
+
+/// How a final consonant and the next initial consonant are pronounced when ㅎ aspirates the
+/// plain consonant next to it, or `None` if they don't interact. Everything is conjoining jamo.
+fn aspirated(final_consonant: char, next_initial: char) -> Option<(Option<char>, char)> {
+    match (final_consonant, next_initial) {
+        // ㅎ then ㄱ ㄷ ㅈ, 좋다 is said 조타
+        ('\u{11C2}' | '\u{11AD}' | '\u{11B6}', '\u{1100}' | '\u{1103}' | '\u{110C}') => {
+            let kept = match final_consonant {
+                '\u{11AD}' => Some('\u{11AB}'),
+                '\u{11B6}' => Some('\u{11AF}'),
+                _ => None,
+            };
+            let next = match next_initial {
+                '\u{1100}' => '\u{110F}',
+                '\u{1103}' => '\u{1110}',
+                _ => '\u{110E}',
+            };
+            Some((kept, next))
+        }
+        // ㄱ ㄷ ㅂ ㅈ (and ㅅ said as ㄷ) then ㅎ, 축하 is said 추카
+        ('\u{11A8}', '\u{1112}') => Some((None, '\u{110F}')),
+        ('\u{11AE}' | '\u{11BA}', '\u{1112}') => Some((None, '\u{1110}')),
+        ('\u{11B8}', '\u{1112}') => Some((None, '\u{1111}')),
+        ('\u{11BD}', '\u{1112}') => Some((None, '\u{110E}')),
+        ('\u{11B0}', '\u{1112}') => Some((Some('\u{11AF}'), '\u{110F}')),
+        ('\u{11B2}', '\u{1112}') => Some((Some('\u{11AF}'), '\u{1111}')),
+        _ => None,
+    }
+}
+
+/// Rewrites each pair of neighbouring Hangul syllables in `graphemes` the way ㅎ aspiration
+/// makes them sound, so 좋다 becomes 조타 and 축하 becomes 추카. Anything that isn't a single
+/// Hangul syllable (after NFC) is left alone and stops the rule applying across it.
+pub(crate) fn aspirate(graphemes: &[&str]) -> Vec<String> {
+    let mut syllables: Vec<Option<(char, char, Option<char>)>> = graphemes
+        .iter()
+        .map(|grapheme| {
+            let mut chars = grapheme.nfc();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => decompose_syllable(c),
+                _ => None,
+            }
+        })
+        .collect();
+
+    for i in 1..syllables.len() {
+        let (Some((_, _, Some(final_consonant))), Some((next_initial, _, _))) =
+            (syllables[i - 1], syllables[i])
+        else {
+            continue;
+        };
+        if let Some((kept, next)) = aspirated(final_consonant, next_initial) {
+            if let Some(previous) = syllables[i - 1].as_mut() {
+                previous.2 = kept;
+            }
+            if let Some(current) = syllables[i].as_mut() {
+                current.0 = next;
+            }
+        }
+    }
+
+    graphemes
+        .iter()
+        .zip(syllables)
+        .map(|(grapheme, syllable)| {
+            match syllable.and_then(|(cho, jung, jong)| compose_syllable(cho, jung, jong)) {
+                Some(c) => String::from(c),
+                None => String::from(*grapheme),
+            }
+        })
+        .collect()
+}
+
+/// The consonant groups from the paper that `k_edit_distance` folds together by default, see
+/// `normalize`. Like `VOWEL_GROUPS` they're written as compatibility jamo.
+pub const CONSONANT_GROUPS: &[&[char]] = &[
+    &['ㄱ', 'ㅋ', 'ㄲ'],
+    &['ㄷ', 'ㄸ', 'ㅌ'],
+    &['ㅂ', 'ㅃ', 'ㅍ'],
+    &['ㅅ', 'ㅆ'],
+    &['ㅈ', 'ㅉ', 'ㅊ'],
+];
+
+/// Which consonants `KEditConfig::consonant_normalization` folds together, for when the
+/// paper's `CONSONANT_GROUPS` aren't the confusions you care about, like a speech recognizer
+/// that mixes up ㄴ and ㅁ. The default is `CONSONANT_GROUPS`.
+///
+/// Each group is written as compatibility jamo and everything in it is folded to the first
+/// member. Leading and trailing conjoining jamo are folded the same way, to the first member
+/// that can be in that position since ㄸ ㅃ ㅉ are never final consonants.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{k_edit_distance_with, ConsonantGroups, KEditConfig};
+///
+/// let config = KEditConfig {
+///     consonant_groups: ConsonantGroups::new(&[&['ㄴ', 'ㅁ']]).unwrap(),
+///     ..Default::default()
+/// };
+/// assert_eq!(k_edit_distance_with("눈", "문", &config), 0.0);
+/// assert!(ConsonantGroups::new(&[&['ㄴ', 'ㅁ'], &['ㅁ', 'ㅇ']]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "Vec<Vec<char>>", into = "Vec<Vec<char>>")
+)]
+pub struct ConsonantGroups {
+    groups: Vec<Vec<char>>,
+    /// Every jamo that folds to something else, compatibility and conjoining
+    folds: BTreeMap<char, char>,
+}
+
+impl ConsonantGroups {
+    /// Folds each of `groups` to its first member, or `DuplicateJamo` if a jamo is in more
+    /// than one of them.
+    pub fn new<G: AsRef<[char]>>(groups: &[G]) -> Result<Self, DuplicateJamo> {
+        let mut folds = BTreeMap::new();
+        let mut seen = BTreeMap::new();
+        for (i, group) in groups.iter().enumerate() {
+            let group = group.as_ref();
+            for &jamo in group {
+                if *seen.entry(jamo).or_insert(i) != i {
+                    return Err(DuplicateJamo { jamo });
+                }
+            }
+
+            let leading = group.iter().find_map(|&c| leading_jamo(c));
+            let trailing = group.iter().find_map(|&c| trailing_jamo(c));
+            for &jamo in group {
+                folds.insert(jamo, group[0]);
+                if let (Some(from), Some(to)) = (leading_jamo(jamo), leading) {
+                    folds.insert(from, to);
+                }
+                if let (Some(from), Some(to)) = (trailing_jamo(jamo), trailing) {
+                    folds.insert(from, to);
+                }
+            }
+        }
+
+        Ok(Self {
+            groups: groups.iter().map(|group| group.as_ref().to_vec()).collect(),
+            folds,
+        })
+    }
+
+    /// The groups this was made from
+    pub fn groups(&self) -> &[Vec<char>] {
+        &self.groups
+    }
+
+    /// What `c` is folded to, `c` itself if it isn't in any group
+    pub fn fold(&self, c: char) -> char {
+        self.folds.get(&c).copied().unwrap_or(c)
+    }
+}
+
+impl Default for ConsonantGroups {
+    fn default() -> Self {
+        Self::new(CONSONANT_GROUPS).unwrap()
+    }
+}
+
+impl TryFrom<Vec<Vec<char>>> for ConsonantGroups {
+    type Error = DuplicateJamo;
+
+    fn try_from(groups: Vec<Vec<char>>) -> Result<Self, Self::Error> {
+        Self::new(&groups)
+    }
+}
+
+impl From<ConsonantGroups> for Vec<Vec<char>> {
+    fn from(groups: ConsonantGroups) -> Self {
+        groups.groups
+    }
+}
+
+/// Returned by `ConsonantGroups::new` when `jamo` is in more than one group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateJamo {
+    pub jamo: char,
+}
+
+impl core::fmt::Display for DuplicateJamo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} is in more than one consonant group", self.jamo)
+    }
+}
+
+impl core::error::Error for DuplicateJamo {}
+
+/// The leading conjoining jamo for the compatibility consonant `c`, if it can start a syllable
+fn leading_jamo(c: char) -> Option<char> {
+    let index = CHOSEONG_COMPATIBILITY.iter().position(|&jamo| jamo == c)?;
+    char::from_u32(L_BASE + index as u32)
+}
+
+/// The trailing conjoining jamo for the compatibility consonant `c`, if it can end a syllable
+fn trailing_jamo(c: char) -> Option<char> {
+    let index = JONGSEONG_COMPATIBILITY.iter().position(|&jamo| jamo == c)?;
+    char::from_u32(T_BASE + 1 + index as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use unicode_normalization::UnicodeNormalization;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[test]
+    fn test_decompose_syllable() {
+        assert_eq!(
+            decompose_syllable('각'),
+            Some(('\u{1100}', '\u{1161}', Some('\u{11A8}')))
+        );
+        assert_eq!(
+            decompose_syllable('가'),
+            Some(('\u{1100}', '\u{1161}', None))
+        );
+        assert_eq!(
+            decompose_syllable('힣'),
+            Some(('\u{1112}', '\u{1175}', Some('\u{11C2}')))
+        );
+        assert_eq!(
+            decompose_syllable('한'),
+            Some(('\u{1112}', '\u{1161}', Some('\u{11AB}')))
+        );
+        assert_eq!(decompose_syllable('ㄱ'), None);
+        assert_eq!(decompose_syllable('\u{1100}'), None);
+        assert_eq!(decompose_syllable('\u{D7A4}'), None);
+        assert_eq!(decompose_syllable('a'), None);
+
+        // Should agree with NFD for every syllable
+        for c in '가'..='힣' {
+            let (l, v, t) = decompose_syllable(c).unwrap();
+            let expected: Vec<char> = c.to_string().nfd().collect();
+            let actual: Vec<char> = [Some(l), Some(v), t].into_iter().flatten().collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_compose_syllable() {
+        assert_eq!(
+            compose_syllable('\u{1100}', '\u{1161}', Some('\u{11A8}')),
+            Some('각')
+        );
+        assert_eq!(compose_syllable('\u{1100}', '\u{1161}', None), Some('가'));
+        assert_eq!(
+            compose_syllable('\u{1112}', '\u{1175}', Some('\u{11C2}')),
+            Some('힣')
+        );
+        // Compatibility jamo aren't accepted
+        assert_eq!(compose_syllable('ㄱ', 'ㅏ', None), None);
+        assert_eq!(compose_syllable('\u{1100}', '\u{1100}', None), None);
+        assert_eq!(compose_syllable('\u{1161}', '\u{1161}', None), None);
+        assert_eq!(
+            compose_syllable('\u{1100}', '\u{1161}', Some('\u{11A7}')),
+            None
+        );
+        assert_eq!(
+            compose_syllable('\u{1100}', '\u{1161}', Some('\u{11C3}')),
+            None
+        );
+
+        let round_trip: String = "한글"
+            .chars()
+            .map(|c| {
+                let (cho, jung, jong) = decompose_syllable(c).unwrap();
+                compose_syllable(cho, jung, jong).unwrap()
+            })
+            .collect();
+        assert_eq!(round_trip, "한글");
+
+        for c in '가'..='힣' {
+            let (cho, jung, jong) = decompose_syllable(c).unwrap();
+            assert_eq!(compose_syllable(cho, jung, jong), Some(c));
+        }
+    }
+
+    #[test]
+    fn test_compatibility_jamo() {
+        let jamo: String = "각 의 없".nfd().map(compatibility_jamo).collect();
+        assert_eq!(jamo, "ㄱㅏㄱ ㅇㅢ ㅇㅓㅄ");
+        assert_eq!(compatibility_jamo('ㄱ'), 'ㄱ');
+        assert_eq!(compatibility_jamo('a'), 'a');
+    }
+
+    #[test]
+    fn test_split_double_final() {
+        let split = |s: &str| -> String {
+            s.chars()
+                .flat_map(|c| match split_double_final(c) {
+                    Some((first, second)) => [first, second].into_iter().collect::<Vec<_>>(),
+                    None => vec![c],
+                })
+                .collect()
+        };
+
+        for (double, parts) in [
+            ("ㄳ", "ㄱㅅ"),
+            ("ㄵ", "ㄴㅈ"),
+            ("ㄶ", "ㄴㅎ"),
+            ("ㄺ", "ㄹㄱ"),
+            ("ㄻ", "ㄹㅁ"),
+            ("ㄼ", "ㄹㅂ"),
+            ("ㄽ", "ㄹㅅ"),
+            ("ㄾ", "ㄹㅌ"),
+            ("ㄿ", "ㄹㅍ"),
+            ("ㅀ", "ㄹㅎ"),
+            ("ㅄ", "ㅂㅅ"),
+        ] {
+            assert_eq!(split(double), parts);
+        }
+
+        // Conjoining trailing consonants split into trailing consonants
+        let (_, _, jong) = decompose_syllable('닭').unwrap();
+        assert_eq!(
+            split_double_final(jong.unwrap()),
+            Some(('\u{11AF}', '\u{11A8}'))
+        );
+        let expanded: String = "값 앉 없".nfd().map(|c| split(&c.to_string())).collect();
+        let expanded: String = expanded.chars().map(compatibility_jamo).collect();
+        assert_eq!(expanded, "ㄱㅏㅂㅅ ㅇㅏㄴㅈ ㅇㅓㅂㅅ");
+        assert_eq!(split_double_final('ㄱ'), None);
+        assert_eq!(split_double_final('\u{11A8}'), None);
+    }
+
+    #[test]
+    fn test_aspirate() {
+        let aspirate_str = |s: &str| aspirate(&s.graphemes(true).collect::<Vec<_>>()).concat();
+
+        assert_eq!(aspirate_str("좋다"), "조타");
+        assert_eq!(aspirate_str("놓고"), "노코");
+        assert_eq!(aspirate_str("좋지"), "조치");
+        assert_eq!(aspirate_str("않다"), "안타");
+        assert_eq!(aspirate_str("싫다"), "실타");
+        assert_eq!(aspirate_str("축하"), "추카");
+        assert_eq!(aspirate_str("입학"), "이팍");
+        assert_eq!(aspirate_str("맞히다"), "마치다");
+        assert_eq!(aspirate_str("못하다"), "모타다");
+        assert_eq!(aspirate_str("밝히다"), "발키다");
+        // Nothing to aspirate
+        assert_eq!(aspirate_str("국어"), "국어");
+        assert_eq!(aspirate_str("좋아"), "좋아");
+        // Only between neighbouring syllables
+        assert_eq!(aspirate_str("좋 다"), "좋 다");
+        assert_eq!(
+            aspirate(&["a", "좋", "\u{1103}\u{1161}"]),
+            ["a", "조", "타"]
+        );
+    }
+
+    #[test]
+    fn test_matches_chosung() {
+        assert!(matches_chosung("ㄱㅇ", "국어"));
+        assert!(matches_chosung("ㄱㅇ", "게임"));
+        assert!(matches_chosung("", "국어"));
+        assert!(matches_chosung("ㅎㄱ", "한국어"));
+        assert!(matches_chosung("ㅎㄱㅇ", "한국어"));
+        // Conjoining leading consonants work too
+        assert!(matches_chosung("\u{1100}\u{110B}", "국어"));
+        // Full syllables in the query don't match their initial consonant
+        assert!(!matches_chosung("게임", "국어"));
+        // It's a prefix match, not a subsequence
+        assert!(!matches_chosung("ㄱㅇ", "한국어"));
+        assert!(!matches_chosung("ㄱㅇㅅ", "국어"));
+        assert!(!matches_chosung("ㄲ", "국어"));
+        // Anything that isn't a syllable is compared as is
+        assert!(matches_chosung("ㅇㅇㄷㅇ", "아이디어뱅크"));
+        assert!(matches_chosung("aㅂ", "a반"));
+        assert!(!matches_chosung("ㅂ", "a반"));
+        assert!(matches_chosung("ㄱ", "ㄱ"));
+    }
+
+    #[test]
+    fn test_conjoining_jamo() {
+        assert_eq!(conjoining_jamo('ㄱ'), 'ᄀ');
+        assert_eq!(conjoining_jamo('ㅎ'), 'ᄒ');
+        assert_eq!(conjoining_jamo('ㄳ'), 'ᆪ');
+        assert_eq!(conjoining_jamo('ㅏ'), 'ᅡ');
+        assert_eq!(conjoining_jamo('ㅣ'), 'ᅵ');
+        // Already conjoining, archaic or not jamo at all
+        assert_eq!(conjoining_jamo('ᄀ'), 'ᄀ');
+        assert_eq!(conjoining_jamo('ㅥ'), 'ㅥ');
+        assert_eq!(conjoining_jamo('가'), '가');
+        assert_eq!(conjoining_jamo('a'), 'a');
+        for c in 'ㄱ'..='ㅣ' {
+            assert_eq!(compatibility_jamo(conjoining_jamo(c)), c);
+        }
+    }
+
+    #[test]
+    fn test_consonant_groups() {
+        let groups = ConsonantGroups::default();
+        // Leading, trailing and compatibility
+        assert_eq!(groups.fold('ᄏ'), 'ᄀ');
+        assert_eq!(groups.fold('ᆩ'), 'ᆨ');
+        assert_eq!(groups.fold('ㅋ'), 'ㄱ');
+        assert_eq!(groups.fold('ᄄ'), 'ᄃ');
+        assert_eq!(groups.fold('ᇀ'), 'ᆮ');
+        assert_eq!(groups.fold('ᄂ'), 'ᄂ');
+        assert_eq!(groups.fold('a'), 'a');
+
+        // The first member can't be final so finals fold to the next one
+        let groups = ConsonantGroups::new(&[&['ㄸ', 'ㄷ', 'ㅌ']]).unwrap();
+        assert_eq!(groups.fold('ᄐ'), 'ᄄ');
+        assert_eq!(groups.fold('ᇀ'), 'ᆮ');
+        assert_eq!(groups.fold('ㅌ'), 'ㄸ');
+
+        let groups = ConsonantGroups::new(&[vec!['ㄴ', 'ㅁ']]).unwrap();
+        assert_eq!(groups.fold('ᄆ'), 'ᄂ');
+        assert_eq!(groups.fold('ᆷ'), 'ᆫ');
+        assert_eq!(groups.fold('ᄏ'), 'ᄏ');
+        assert_eq!(groups.groups(), &[vec!['ㄴ', 'ㅁ']]);
+
+        assert_eq!(
+            ConsonantGroups::new(&[&['ㄴ', 'ㅁ'][..], &['ㅇ', 'ㄴ']]),
+            Err(DuplicateJamo { jamo: 'ㄴ' })
+        );
+        // Repeating a jamo in the same group is fine
+        assert!(ConsonantGroups::new(&[&['ㄴ', 'ㄴ']]).is_ok());
+        assert_eq!(
+            ConsonantGroups::new::<&[char]>(&[]).unwrap().fold('ᄏ'),
+            'ᄏ'
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_consonant_groups_serde() {
+        let groups = ConsonantGroups::new(&[vec!['ㄴ', 'ㅁ']]).unwrap();
+        let json = serde_json::to_string(&groups).unwrap();
+        assert_eq!(json, r#"[["ㄴ","ㅁ"]]"#);
+        let parsed = serde_json::from_str::<ConsonantGroups>(&json).unwrap();
+        assert_eq!(parsed, groups);
+        assert_eq!(parsed.fold('ᆷ'), 'ᆫ');
+
+        // The same check as `ConsonantGroups::new`
+        assert!(serde_json::from_str::<ConsonantGroups>(r#"[["ㄴ","ㅁ"],["ㅇ","ㄴ"]]"#).is_err());
+    }
+}
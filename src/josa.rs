@@ -0,0 +1,112 @@
+//! Stripping particles (조사) off the end of Korean nouns so "학교에" compares the same as
+//! "학교".
+
+use crate::{decompose_syllable, k_edit_distance};
+
+/// The particles `strip_josa` takes off, longest first so 에서 wins over 에. Each says whether
+/// it only goes after a final consonant (`Some(true)`), only after a vowel (`Some(false)`) or
+/// after anything (`None`).
+const JOSA: &[(&str, Option<bool>)] = &[
+    ("에서", None),
+    ("은", Some(true)),
+    ("는", Some(false)),
+    ("이", Some(true)),
+    ("가", Some(false)),
+    ("을", Some(true)),
+    ("를", Some(false)),
+    ("에", None),
+];
+
+/// `s` without a particle on the end. Only these are stripped: 은/는, 이/가, 을/를, 에 and 에서.
+///
+/// This is kept conservative so it doesn't eat the end of words that just happen to end in one
+/// of those syllables:
+///
+/// - whatever is left has to end in a Hangul syllable, so single syllable words like "가" are
+///   never stripped
+/// - the pairs that depend on the word before only match the right way around, 은 이 을 after a
+///   final consonant and 는 가 를 after a vowel, so "나이" (age) keeps its 이
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::strip_josa;
+///
+/// assert_eq!(strip_josa("학교에"), "학교");
+/// assert_eq!(strip_josa("학교에서"), "학교");
+/// assert_eq!(strip_josa("책을"), "책");
+/// assert_eq!(strip_josa("가"), "가");
+/// assert_eq!(strip_josa("나이"), "나이");
+/// ```
+pub fn strip_josa(s: &str) -> &str {
+    for (josa, after_consonant) in JOSA {
+        let Some(stem) = s.strip_suffix(josa) else {
+            continue;
+        };
+        let Some((_, _, last_final)) = stem.chars().next_back().and_then(decompose_syllable) else {
+            continue;
+        };
+        if after_consonant.is_none_or(|after_consonant| after_consonant == last_final.is_some()) {
+            return stem;
+        }
+    }
+
+    s
+}
+
+/// `k_edit_distance` after `strip_josa` on both strings.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::k_edit_distance_stemmed("학교에", "학교는"), 0.0);
+/// assert_ne!(k_edit_distance::k_edit_distance("학교에", "학교는"), 0.0);
+/// ```
+pub fn k_edit_distance_stemmed(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    k_edit_distance(strip_josa(s), strip_josa(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_josa() {
+        assert_eq!(strip_josa("학교에"), "학교");
+        assert_eq!(strip_josa("학교에서"), "학교");
+        assert_eq!(strip_josa("학교는"), "학교");
+        assert_eq!(strip_josa("학교가"), "학교");
+        assert_eq!(strip_josa("학교를"), "학교");
+        assert_eq!(strip_josa("책은"), "책");
+        assert_eq!(strip_josa("책이"), "책");
+        assert_eq!(strip_josa("책을"), "책");
+        // The wrong half of the pair for the word before
+        assert_eq!(strip_josa("학교을"), "학교을");
+        assert_eq!(strip_josa("책가"), "책가");
+        assert_eq!(strip_josa("나이"), "나이");
+        // Nothing left to be a stem
+        assert_eq!(strip_josa("가"), "가");
+        assert_eq!(strip_josa("에서"), "에서");
+        assert_eq!(strip_josa("이"), "이");
+        assert_eq!(strip_josa("a이"), "a이");
+        // Only one particle comes off
+        assert_eq!(strip_josa("학교에는"), "학교에");
+        assert_eq!(strip_josa("학교"), "학교");
+        assert_eq!(strip_josa(""), "");
+    }
+
+    #[test]
+    fn test_k_edit_distance_stemmed() {
+        assert_eq!(k_edit_distance_stemmed("학교에", "학교"), 0.);
+        assert_eq!(k_edit_distance_stemmed("책을", "책이"), 0.);
+        assert_eq!(
+            k_edit_distance_stemmed("학교에", "학고"),
+            k_edit_distance("학교", "학고")
+        );
+        assert_eq!(
+            k_edit_distance_stemmed("가", "나"),
+            k_edit_distance("가", "나")
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Typo distances where hitting a key next to the right one costs less than a random mistake.
+
+use alloc::vec::Vec;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::fractional_levenshtein;
+use crate::hangul::compatibility_jamo;
+
+/// What substituting a key for one of its neighbours costs, anything else is 1.0.
+const ADJACENT_COST: f32 = 0.5;
+
+/// The letter keys of a QWERTY keyboard with the keys touching them, including the ones
+/// diagonally above and below on the staggered rows.
+pub const QWERTY_ADJACENCY: &[(char, &str)] = &[
+    ('q', "wa"),
+    ('w', "qeas"),
+    ('e', "wrsd"),
+    ('r', "etdf"),
+    ('t', "ryfg"),
+    ('y', "tugh"),
+    ('u', "yihj"),
+    ('i', "uojk"),
+    ('o', "ipkl"),
+    ('p', "ol"),
+    ('a', "qwsz"),
+    ('s', "weadzx"),
+    ('d', "ersfxc"),
+    ('f', "rtdgcv"),
+    ('g', "tyfhvb"),
+    ('h', "yugjbn"),
+    ('j', "uihknm"),
+    ('k', "iojlm"),
+    ('l', "opk"),
+    ('z', "asx"),
+    ('x', "zsdc"),
+    ('c', "xdfv"),
+    ('v', "cfgb"),
+    ('b', "vghn"),
+    ('n', "bhjm"),
+    ('m', "njk"),
+];
+
+/// Levenshtein distance where substituting a key for one next to it on a QWERTY keyboard costs
+/// 0.5 instead of 1.0. Inserting and deleting cost 1.0 and upper case letters count as the same
+/// key as their lower case.
+///
+/// # Examples
+///
+/// ```
+/// // 'w' is next to 'e' but 'x' isn't
+/// assert_eq!(k_edit_distance::keyboard_levenshtein("hwllo", "hello"), 0.5);
+/// assert_eq!(k_edit_distance::keyboard_levenshtein("hxllo", "hello"), 1.0);
+/// ```
+pub fn keyboard_levenshtein(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    keyboard_levenshtein_with(s, t, QWERTY_ADJACENCY)
+}
+
+/// `keyboard_levenshtein` with a different layout, given as each key with the keys next to it
+/// like `QWERTY_ADJACENCY`. Two keys count as adjacent if either one lists the other.
+///
+/// # Examples
+///
+/// ```
+/// // the top row of a Dvorak keyboard
+/// let dvorak = &[('p', "yg"), ('y', "pf"), ('f', "yg"), ('g', "fc")];
+/// assert_eq!(k_edit_distance::keyboard_levenshtein_with("pf", "yf", dvorak), 0.5);
+/// assert_eq!(k_edit_distance::keyboard_levenshtein_with("ff", "gg", dvorak), 1.0);
+/// ```
+pub fn keyboard_levenshtein_with(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    layout: &[(char, &str)],
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+    fractional_levenshtein(&s, &t, |a, b| {
+        if adjacent(layout, a.to_ascii_lowercase(), b.to_ascii_lowercase()) {
+            ADJACENT_COST
+        } else {
+            1.0
+        }
+    })
+}
+
+/// The jamo keys of the standard two-set (Dubeolsik, 두벌식) Korean keyboard with the keys
+/// touching them, the same physical keys as `QWERTY_ADJACENCY`. The doubled consonants and
+/// ㅒ ㅖ are typed with shift on the key of their plain version so they aren't listed.
+pub const DUBEOLSIK_ADJACENCY: &[(char, &str)] = &[
+    ('ㅂ', "ㅈㅁ"),
+    ('ㅈ', "ㅂㄷㅁㄴ"),
+    ('ㄷ', "ㅈㄱㄴㅇ"),
+    ('ㄱ', "ㄷㅅㅇㄹ"),
+    ('ㅅ', "ㄱㅛㄹㅎ"),
+    ('ㅛ', "ㅅㅕㅎㅗ"),
+    ('ㅕ', "ㅛㅑㅗㅓ"),
+    ('ㅑ', "ㅕㅐㅓㅏ"),
+    ('ㅐ', "ㅑㅔㅏㅣ"),
+    ('ㅔ', "ㅐㅣ"),
+    ('ㅁ', "ㅂㅈㄴㅋ"),
+    ('ㄴ', "ㅈㄷㅁㅇㅋㅌ"),
+    ('ㅇ', "ㄷㄱㄴㄹㅌㅊ"),
+    ('ㄹ', "ㄱㅅㅇㅎㅊㅍ"),
+    ('ㅎ', "ㅅㅛㄹㅗㅍㅠ"),
+    ('ㅗ', "ㅛㅕㅎㅓㅠㅜ"),
+    ('ㅓ', "ㅕㅑㅗㅏㅜㅡ"),
+    ('ㅏ', "ㅑㅐㅓㅣㅡ"),
+    ('ㅣ', "ㅐㅔㅏ"),
+    ('ㅋ', "ㅁㄴㅌ"),
+    ('ㅌ', "ㅋㄴㅇㅊ"),
+    ('ㅊ', "ㅌㅇㄹㅍ"),
+    ('ㅍ', "ㅊㄹㅎㅠ"),
+    ('ㅠ', "ㅍㅎㅗㅜ"),
+    ('ㅜ', "ㅠㅗㅓㅡ"),
+    ('ㅡ', "ㅜㅓㅏ"),
+];
+
+/// Levenshtein distance between the jamo of `s` and `t` where substituting a jamo for one on a
+/// key next to it on a Dubeolsik keyboard costs 0.5 instead of 1.0, so does a missed or extra
+/// shift like ㄲ for ㄱ. Inserting and deleting a jamo cost 1.0.
+///
+/// Syllables are split into compatibility jamo first, compound vowels and final consonants
+/// like ㅘ or ㄳ are left as one jamo and only cost less against themselves.
+///
+/// # Examples
+///
+/// ```
+/// // ㅁ and ㄴ are next to each other, ㅁ and ㄹ aren't
+/// assert_eq!(k_edit_distance::korean_keyboard_distance("감사", "간사"), 0.5);
+/// assert_eq!(k_edit_distance::korean_keyboard_distance("감사", "갈사"), 1.0);
+/// ```
+pub fn korean_keyboard_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    korean_keyboard_distance_with(s, t, DUBEOLSIK_ADJACENCY)
+}
+
+/// `korean_keyboard_distance` with a different layout, given as each jamo with the jamo next to
+/// it like `DUBEOLSIK_ADJACENCY`.
+pub fn korean_keyboard_distance_with(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    layout: &[(char, &str)],
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.nfd().map(compatibility_jamo).collect();
+    let t: Vec<char> = t.nfd().map(compatibility_jamo).collect();
+    fractional_levenshtein(&s, &t, |a, b| {
+        let (a, b) = (unshifted(*a), unshifted(*b));
+        if a == b || adjacent(layout, a, b) {
+            ADJACENT_COST
+        } else {
+            1.0
+        }
+    })
+}
+
+/// The jamo on the same Dubeolsik key without shift
+fn unshifted(c: char) -> char {
+    match c {
+        'ㅃ' => 'ㅂ',
+        'ㅉ' => 'ㅈ',
+        'ㄸ' => 'ㄷ',
+        'ㄲ' => 'ㄱ',
+        'ㅆ' => 'ㅅ',
+        'ㅒ' => 'ㅐ',
+        'ㅖ' => 'ㅔ',
+        _ => c,
+    }
+}
+
+fn adjacent(layout: &[(char, &str)], a: char, b: char) -> bool {
+    layout.iter().any(|(key, neighbours)| {
+        (*key == a && neighbours.contains(b)) || (*key == b && neighbours.contains(a))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyboard_levenshtein() {
+        assert!(keyboard_levenshtein("hwllo", "hello") < keyboard_levenshtein("hxllo", "hello"));
+        assert_eq!(keyboard_levenshtein("sog", "dog"), 0.5);
+        assert_eq!(keyboard_levenshtein("pog", "dog"), 1.0);
+        assert_eq!(keyboard_levenshtein("Sog", "dog"), 0.5);
+        assert_eq!(keyboard_levenshtein("hello", "hello"), 0.0);
+        assert_eq!(keyboard_levenshtein("hello", "helo"), 1.0);
+        assert_eq!(keyboard_levenshtein("", "abc"), 3.0);
+        assert_eq!(keyboard_levenshtein("국어", "숙어"), 1.0);
+
+        // every neighbour lists the key back
+        for (key, neighbours) in QWERTY_ADJACENCY {
+            for neighbour in neighbours.chars() {
+                let (_, back) = QWERTY_ADJACENCY
+                    .iter()
+                    .find(|(other, _)| *other == neighbour)
+                    .unwrap();
+                assert!(back.contains(*key), "{key} {neighbour}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_korean_keyboard_distance() {
+        assert!(
+            korean_keyboard_distance("감사", "간사") < korean_keyboard_distance("감사", "갈사")
+        );
+        assert_eq!(korean_keyboard_distance("감사", "간사"), 0.5);
+        // ㅓ and ㅏ are neighbours
+        assert_eq!(korean_keyboard_distance("거", "가"), 0.5);
+        assert_eq!(korean_keyboard_distance("까", "가"), 0.5);
+        assert_eq!(korean_keyboard_distance("국어", "국어"), 0.0);
+        assert_eq!(korean_keyboard_distance("국어", "국"), 2.0);
+        assert_eq!(korean_keyboard_distance("ㄱㅏ", "가"), 0.0);
+        assert_eq!(korean_keyboard_distance("없", "업"), 1.0);
+
+        for (key, neighbours) in DUBEOLSIK_ADJACENCY {
+            for neighbour in neighbours.chars() {
+                let (_, back) = DUBEOLSIK_ADJACENCY
+                    .iter()
+                    .find(|(other, _)| *other == neighbour)
+                    .unwrap();
+                assert!(back.contains(*key), "{key} {neighbour}");
+            }
+        }
+    }
+}
@@ -1,135 +1,3257 @@
-#![feature(test)]
-extern crate test;
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::char;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategoryGroup, UnicodeGeneralCategory};
+use unicode_script::{Script, UnicodeScript};
+use unicode_segmentation::UnicodeSegmentation;
+
+use low_level::levenshtein_distance_chars;
+
+mod align;
+mod confusion;
+mod diff;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod hangul;
+mod josa;
+mod keyboard;
+pub mod low_level;
+mod metric;
+mod ngram;
+mod phonetic;
+mod romanize;
+mod search;
+mod subsequence;
+mod token;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use align::{Alignment, gotoh_align, needleman_wunsch, smith_waterman};
+pub use confusion::{ConfusionMatrix, confusion_weighted_distance};
+pub use diff::{colored_diff, colored_diff_no_ansi, format_alignment};
+pub use hangul::{
+    CONSONANT_GROUPS, ConsonantGroups, DuplicateJamo, compose_syllable, decompose_syllable,
+    matches_chosung,
+};
+pub use josa::{k_edit_distance_stemmed, strip_josa};
+pub use keyboard::{
+    DUBEOLSIK_ADJACENCY, QWERTY_ADJACENCY, keyboard_levenshtein, keyboard_levenshtein_with,
+    korean_keyboard_distance, korean_keyboard_distance_with,
+};
+pub use metric::{
+    CachedDistance, DamerauMetric, DistanceMetric, KEditMetric, LevenshteinMetric, OsaMetric,
+};
+pub use ngram::{
+    cosine_ngram, dice_coefficient, jaccard_ngram, overlap_coefficient, qgram_distance,
+    tversky_index,
+};
+pub use phonetic::{metaphone, metaphone_equal, soundex, soundex_equal};
+pub use romanize::{
+    RomanizationScheme, cross_script_distance, cross_script_distance_with,
+    mccune_reischauer_romanize, revised_romanize,
+};
+pub use search::{
+    BkTree, Corrector, LevAutomaton, SymSpell, best_substring_match, closest, distance_matrix,
+    find_all_within, fuzzy_match_score, k_edit_distance_matches, k_nearest,
+};
+pub use subsequence::{lcs, lcs_length, longest_common_substring, sequence_matcher_ratio};
+pub use token::{token_damerau, token_levenshtein, token_levenshtein_ci};
+
+pub fn levenshtein_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    weighted_levenshtein(s, t, 1, 1, 1)
+}
+
+/// The most DP cells, `len(s) * len(t)` in `char`s, `levenshtein_checked` fills in before giving
+/// up. A hundred million takes well under a second, two strings of 10,000 `char`s each.
+pub const DEFAULT_CELL_LIMIT: usize = 100_000_000;
+
+/// `levenshtein_distance`, or an error instead of running for minutes when the strings are
+/// so long that `len(s) * len(t)` is over `DEFAULT_CELL_LIMIT`. Use
+/// `levenshtein_checked_with_limit` to raise or lower the limit.
+///
+/// Memory isn't the problem, only two rows of the DP are kept so it's O(len(t)), but the time
+/// still grows with the product of the lengths. This is for when the strings come from users
+/// and a huge one shouldn't be able to hang whatever is comparing them.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::levenshtein_checked;
+///
+/// assert_eq!(levenshtein_checked("kitten", "sitting"), Ok(3));
+///
+/// let huge = "a".repeat(100_000);
+/// assert!(levenshtein_checked(&huge, &huge).is_err());
+/// ```
+pub fn levenshtein_checked(s: impl AsRef<str>, t: impl AsRef<str>) -> Result<usize, DistanceError> {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_checked_with_limit(s, t, DEFAULT_CELL_LIMIT)
+}
+
+/// `levenshtein_checked` with at most `max_cells` instead of `DEFAULT_CELL_LIMIT`.
+/// `usize::MAX` never gives up.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::levenshtein_checked_with_limit;
+///
+/// assert_eq!(levenshtein_checked_with_limit("kitten", "sitting", 42), Ok(3));
+/// assert!(levenshtein_checked_with_limit("kitten", "sitting", 41).is_err());
+/// ```
+pub fn levenshtein_checked_with_limit(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    max_cells: usize,
+) -> Result<usize, DistanceError> {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s = s.chars().collect::<Vec<_>>();
+    let t = t.chars().collect::<Vec<_>>();
+    let error = DistanceError {
+        s_len: s.len(),
+        t_len: t.len(),
+        max_cells,
+    };
+    match s.len().checked_mul(t.len()) {
+        Some(cells) if cells <= max_cells => Ok(levenshtein_slice(&s, &t)),
+        _ => Err(error),
+    }
+}
+
+/// Returned by `levenshtein_checked` when the strings are too long to compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceError {
+    pub s_len: usize,
+    pub t_len: usize,
+    pub max_cells: usize,
+}
+
+impl core::fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "strings are too long to compare ({} and {} chars, at most {} cells)",
+            self.s_len, self.t_len, self.max_cells
+        )
+    }
+}
+
+impl core::error::Error for DistanceError {}
+
+/// Returns 1.0 for exactly the same and 0.0 for most different, the Levenshtein distance divided
+/// by the length of the longer string and subtracted from 1. Lengths are counted in `char`s like
+/// `levenshtein_distance`. Two empty strings are exactly the same.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::levenshtein_ratio("hello", "hello"), 1.0);
+/// assert_eq!(k_edit_distance::levenshtein_ratio("book", "back"), 0.5);
+/// assert_eq!(k_edit_distance::levenshtein_ratio("abc", "xyz"), 0.0);
+/// ```
+pub fn levenshtein_ratio(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s = s.chars().collect::<Vec<_>>();
+    let t = t.chars().collect::<Vec<_>>();
+
+    let max = s.len().max(t.len());
+    if max == 0 {
+        return 1.;
+    }
+
+    1. - weighted_levenshtein_chars(&s, &t, 1, 1, 1) as f32 / max as f32
+}
+
+/// `levenshtein_ratio` with an extra `length_penalty * |len(s) - len(t)| / max_len` taken off,
+/// for when strings of very different lengths should look less alike than their edit distance
+/// says. It doesn't go below 0.0 and a `length_penalty` of 0.0 is plain `levenshtein_ratio`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{levenshtein_ratio, levenshtein_ratio_with_penalty};
+///
+/// assert_eq!(levenshtein_ratio("abcd", "abcdef"), 1.0 - 2.0 / 6.0);
+/// assert_eq!(levenshtein_ratio_with_penalty("abcd", "abcdef", 0.5), 1.0 - 3.0 / 6.0);
+/// // Same length so there's nothing to penalize
+/// assert_eq!(levenshtein_ratio_with_penalty("book", "back", 0.5), 0.5);
+/// ```
+pub fn levenshtein_ratio_with_penalty(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    length_penalty: f32,
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    if length_penalty == 0. {
+        return levenshtein_ratio(s, t);
+    }
+    let s = s.chars().collect::<Vec<_>>();
+    let t = t.chars().collect::<Vec<_>>();
+
+    let max = s.len().max(t.len());
+    if max == 0 {
+        return 1.;
+    }
+
+    let distance = weighted_levenshtein_chars(&s, &t, 1, 1, 1) as f64 / max as f64;
+    let penalty = length_penalty_for(s.len(), t.len(), length_penalty);
+    ((1. - distance - penalty) as f32).max(0.)
+}
+
+/// What `length_penalty` adds to a normalized distance for strings of these lengths
+fn length_penalty_for(s_len: usize, t_len: usize, length_penalty: f32) -> f64 {
+    let max = s_len.max(t_len);
+    if max == 0 {
+        return 0.;
+    }
+    length_penalty as f64 * s_len.abs_diff(t_len) as f64 / max as f64
+}
+
+/// Levenshtein distance counting grapheme clusters (what a reader would call a character, the
+/// same split `k_edit_distance` uses for syllables) instead of `char`s.
+///
+/// Clusters are the same if they're canonically equivalent, so an "e" followed by a combining
+/// acute accent matches a precomposed "é". `levenshtein_distance` would count that as 2 edits,
+/// one for the different base `char` and one for the extra accent.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::levenshtein_graphemes("cafe\u{301}", "café"), 0);
+/// assert_eq!(k_edit_distance::levenshtein_distance("cafe\u{301}", "café"), 2);
+/// assert_eq!(k_edit_distance::levenshtein_graphemes("👍🏽", "👍"), 1);
+/// ```
+pub fn levenshtein_graphemes(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_generic(
+        &s.graphemes(true).collect::<Vec<_>>(),
+        &t.graphemes(true).collect::<Vec<_>>(),
+        1,
+        1,
+        |a, b| if a.nfc().eq(b.nfc()) { 0 } else { 1 },
+    )
+}
+
+/// `levenshtein_distance` ignoring case, both strings are lowercased with `char::to_lowercase`
+/// first. Some characters lowercase to several `char`s (like "İ" to "i̇") which are all kept.
+///
+/// This is lowercasing, not full Unicode case folding, so "ß" and "SS" are still different.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::levenshtein_distance_ci("Hello", "hELLO"), 0);
+/// assert_eq!(k_edit_distance::levenshtein_distance_ci("Book", "BACK"), 2);
+/// ```
+pub fn levenshtein_distance_ci(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_distance_chars(
+        &s.chars().flat_map(char::to_lowercase).collect::<Vec<_>>(),
+        &t.chars().flat_map(char::to_lowercase).collect::<Vec<_>>(),
+    )
+}
+
+/// Like `levenshtein_distance` but accents and other diacritics are ignored, so "café" and
+/// "cafe" are the same. Both strings are decomposed with NFD, the combining diacritical marks
+/// (U+0300 to U+036F) are dropped and what's left is composed again with NFC, so Hangul
+/// syllables still count as one `char` each.
+///
+/// That only removes marks, it doesn't transliterate letters so "ß" and "ø" stay as they are.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::levenshtein_distance_ascii_fold("café", "cafe"), 0);
+/// assert_eq!(k_edit_distance::levenshtein_distance_ascii_fold("Zürich", "Zurich"), 0);
+/// assert_eq!(k_edit_distance::levenshtein_distance_ascii_fold("straße", "strasse"), 2);
+/// ```
+pub fn levenshtein_distance_ascii_fold(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let strip = |s: &str| -> Vec<char> {
+        s.nfd()
+            .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+            .nfc()
+            .collect()
+    };
+    levenshtein_distance_chars(&strip(s), &strip(t))
+}
+
+/// Options for `levenshtein_distance_with`, the default is plain `levenshtein_distance`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LevenshteinConfig {
+    /// Treat the Halfwidth and Fullwidth Forms (U+FF00 to U+FFEF) as the characters they're
+    /// wider or narrower versions of, so "ＡＢ" is the same as "AB", "ｶﾞ" as "ガ" and "ﾡ" as
+    /// "ㄱ". The ideographic space U+3000 counts as a normal space too.
+    pub fold_width: bool,
+    /// Drop every character in the Unicode punctuation categories before comparing, so
+    /// "U.S.A." is the same as "USA" and "don't" as "dont". That's connector (Pc), dash (Pd),
+    /// open (Ps) and close (Pe) punctuation, initial (Pi) and final (Pf) quotes and other
+    /// punctuation (Po). Symbols like `+` or `$` aren't punctuation and are kept. This happens
+    /// after `fold_width` so full width punctuation is dropped too.
+    pub strip_punctuation: bool,
+    /// Trim whitespace off both ends and turn every run of whitespace (anything
+    /// `char::is_whitespace`) left into one ASCII space, so "hello \t  world " is the same as
+    /// "hello world". It's done last so the space left by stripped punctuation is collapsed too.
+    /// This is separate from `KEditConfig::strip_whitespace`, which drops whitespace entirely.
+    pub collapse_whitespace: bool,
+}
+
+/// `levenshtein_distance` with the options in `config`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{levenshtein_distance_with, LevenshteinConfig};
+///
+/// let config = LevenshteinConfig {
+///     fold_width: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(levenshtein_distance_with("ＡＢ１２", "AB12", &config), 0);
+/// assert_eq!(k_edit_distance::levenshtein_distance("ＡＢ１２", "AB12"), 4);
+/// ```
+pub fn levenshtein_distance_with(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    config: &LevenshteinConfig,
+) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_distance_chars(&preprocess(s, config), &preprocess(t, config))
+}
+
+/// The `char`s of `s` with the options in `config` applied
+fn preprocess(s: &str, config: &LevenshteinConfig) -> Vec<char> {
+    let folded;
+    let s = if config.fold_width {
+        folded = fold_width(s);
+        &folded
+    } else {
+        s
+    };
+
+    let chars = s.chars().filter(|c| {
+        !config.strip_punctuation || c.general_category_group() != GeneralCategoryGroup::Punctuation
+    });
+    if !config.collapse_whitespace {
+        return chars.collect();
+    }
+
+    let mut collapsed = vec![];
+    let mut space = false;
+    for c in chars {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        // Only put the space in once there's something after it, so the end is trimmed
+        if space && !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        space = false;
+        collapsed.push(c);
+    }
+    collapsed
+}
+
+/// Replaces the Halfwidth and Fullwidth Forms and the ideographic space with their normal
+/// width characters. Half width voicing marks are put back on the kana before them and half
+/// width Hangul jamo become compatibility jamo.
+fn fold_width(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        if !matches!(c, '\u{FF00}'..='\u{FFEF}' | '\u{3000}') {
+            folded.push(c);
+            continue;
+        }
+
+        let half_width_jamo = ('\u{FFA0}'..='\u{FFDC}').contains(&c);
+        for c in core::iter::once(c).nfkc() {
+            // NFKC goes all the way to conjoining jamo, keep them as the jamo a keyboard types
+            if half_width_jamo {
+                folded.push(hangul::compatibility_jamo(c));
+                continue;
+            }
+            let composed = folded
+                .chars()
+                .next_back()
+                .and_then(|previous| unicode_normalization::char::compose(previous, c));
+            match composed {
+                Some(composed) => {
+                    folded.pop();
+                    folded.push(composed);
+                }
+                None => folded.push(c),
+            }
+        }
+    }
+    folded
+}
+
+/// Levenshtein distance between any two sequences, e.g. words in a sentence or bytes.
+/// `levenshtein_distance` is this over the `char`s of each string.
+///
+/// # Examples
+///
+/// ```
+/// let s = ["the", "cat", "sat"];
+/// let t = ["the", "dog", "sat", "down"];
+/// assert_eq!(k_edit_distance::levenshtein_slice(&s, &t), 2);
+/// assert_eq!(k_edit_distance::levenshtein_slice(b"book", b"back"), 2);
+/// ```
+pub fn levenshtein_slice<T: PartialEq>(s: &[T], t: &[T]) -> usize {
+    levenshtein_generic(s, t, 1, 1, |_, _| 1)
+}
+
+/// `levenshtein_slice` on anything that can be iterated over, like `str::chars()` or the
+/// tokens coming out of a parser, without collecting both sides first. Only `t` is collected,
+/// the items of `s` go through the same DP as `levenshtein_generic` as they come and only two
+/// rows of it are kept, so memory is O(n) in the length of `t`. Put the longer one first.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::levenshtein_iter;
+///
+/// assert_eq!(levenshtein_iter("kitten".chars(), "sitting".chars()), 3);
+/// assert_eq!(levenshtein_iter([1, 2, 3], vec![1, 3]), 1);
+/// ```
+pub fn levenshtein_iter<I, J, T>(s: I, t: J) -> usize
+where
+    I: IntoIterator<Item = T>,
+    J: IntoIterator<Item = T>,
+    T: PartialEq,
+{
+    let t: Vec<T> = t.into_iter().collect();
+    levenshtein_in_rows(s, &t, 1, 1, |_, _| 1, &mut vec![], &mut vec![])
+}
+
+/// The full Wagner-Fischer matrix, `d[i][j]` is the distance between `s[..i]` and `t[..j]`.
+/// Each row is a `next_row` of the one before, all of them are kept for tracing back.
+fn levenshtein_matrix<T: PartialEq>(s: &[T], t: &[T]) -> Vec<Vec<usize>> {
+    let n = t.len();
+    let mut d = Vec::with_capacity(s.len() + 1);
+    d.push((0..=n).collect::<Vec<_>>());
+
+    for s_item in s {
+        let mut row = vec![0; n + 1];
+        next_row(&d[d.len() - 1], &mut row, s_item, t, 1, 1, &|_, _| 1);
+        d.push(row);
+    }
+
+    d
+}
+
+/// A single step for turning one string into another, see `levenshtein_ops`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EditOp {
+    /// The character is the same in both strings
+    Match(char),
+    /// The first character is replaced with the second
+    Substitute(char, char),
+    /// The character is only in the second string
+    Insert(char),
+    /// The character is only in the first string
+    Delete(char),
+}
+
+/// The edit operations that turn `s` into `t`, in order. The number of operations that aren't
+/// `EditOp::Match` is always `levenshtein_distance(s, t)`.
+///
+/// When there are several equally short scripts the one picked is found by tracing back from the
+/// end of both strings, preferring a match, then a substitution, then a deletion and finally an
+/// insertion at each step.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::EditOp;
+///
+/// assert_eq!(
+///     k_edit_distance::levenshtein_ops("cat", "cut"),
+///     vec![EditOp::Match('c'), EditOp::Substitute('a', 'u'), EditOp::Match('t')]
+/// );
+/// ```
+pub fn levenshtein_ops(s: impl AsRef<str>, t: impl AsRef<str>) -> Vec<EditOp> {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_ops_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+    )
+}
+
+fn levenshtein_ops_chars(s: &[char], t: &[char]) -> Vec<EditOp> {
+    let d = levenshtein_matrix(s, t);
+    let mut ops = vec![];
+
+    let (mut i, mut j) = (s.len(), t.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && s[i - 1] == t[j - 1] && d[i][j] == d[i - 1][j - 1] {
+            ops.push(EditOp::Match(s[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + 1 {
+            ops.push(EditOp::Substitute(s[i - 1], t[j - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            ops.push(EditOp::Delete(s[i - 1]));
+            i -= 1;
+        } else {
+            ops.push(EditOp::Insert(t[j - 1]));
+            j -= 1;
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// The same kind of edit script as `levenshtein_ops` but found with Hirschberg's divide and
+/// conquer, which only ever keeps a couple of rows of the matrix around instead of all of it.
+/// It takes about twice as long but memory is linear in the length of the shorter input, so it
+/// works for strings far too long for the full matrix.
+///
+/// The number of edits is always `levenshtein_distance`, but when there are several equally
+/// short scripts it may pick a different one to `levenshtein_ops`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::EditOp;
+///
+/// let s: Vec<char> = "cat".chars().collect();
+/// let t: Vec<char> = "cut".chars().collect();
+/// assert_eq!(
+///     k_edit_distance::levenshtein_ops_hirschberg(&s, &t),
+///     vec![EditOp::Match('c'), EditOp::Substitute('a', 'u'), EditOp::Match('t')]
+/// );
+/// ```
+pub fn levenshtein_ops_hirschberg(s: &[char], t: &[char]) -> Vec<EditOp> {
+    let mut ops = vec![];
+    if t.len() > s.len() {
+        // Keep the rows as short as possible by running it the other way around
+        hirschberg(t, s, &mut ops);
+        for op in &mut ops {
+            *op = match *op {
+                EditOp::Match(c) => EditOp::Match(c),
+                EditOp::Substitute(a, b) => EditOp::Substitute(b, a),
+                EditOp::Insert(c) => EditOp::Delete(c),
+                EditOp::Delete(c) => EditOp::Insert(c),
+            };
+        }
+    } else {
+        hirschberg(s, t, &mut ops);
+    }
+    ops
+}
+
+fn hirschberg(s: &[char], t: &[char], ops: &mut Vec<EditOp>) {
+    if s.is_empty() {
+        ops.extend(t.iter().map(|c| EditOp::Insert(*c)));
+        return;
+    }
+    if t.is_empty() {
+        ops.extend(s.iter().map(|c| EditOp::Delete(*c)));
+        return;
+    }
+    if s.len() == 1 || t.len() == 1 {
+        // The matrix is only a couple of rows or columns here
+        ops.extend(levenshtein_ops_chars(s, t));
+        return;
+    }
+
+    // Find where the best path crosses the middle row of s
+    let mid = s.len() / 2;
+    let t_reversed: Vec<char> = t.iter().rev().copied().collect();
+    let forward = levenshtein_last_row(s[..mid].iter(), t);
+    let backward = levenshtein_last_row(s[mid..].iter().rev(), &t_reversed);
+    let split = (0..=t.len())
+        .min_by_key(|&j| forward[j] + backward[t.len() - j])
+        .unwrap();
+
+    hirschberg(&s[..mid], &t[..split], ops);
+    hirschberg(&s[mid..], &t[split..], ops);
+}
+
+/// The last row of the Levenshtein matrix, `row[j]` is the distance between all of `s` and the
+/// first `j` characters of `t`.
+fn levenshtein_last_row<'a>(s: impl Iterator<Item = &'a char>, t: &[char]) -> Vec<usize> {
+    let mut prev = vec![];
+    levenshtein_in_rows(s, t, 1, 1, |_, _| 1, &mut prev, &mut vec![]);
+    prev
+}
+
+/// Levenshtein distance where inserting, deleting and substituting a character each have their
+/// own cost. `levenshtein_distance` is this with every cost set to 1.
+///
+/// Costs are from the point of view of turning `s` into `t`, so `del` is charged for characters
+/// only in `s` and `ins` for characters only in `t`.
+///
+/// # Examples
+///
+/// ```
+/// // Deleting is free so only the insertions into "abc" count
+/// assert_eq!(k_edit_distance::weighted_levenshtein("abc", "abcde", 1, 0, 1), 2);
+/// assert_eq!(k_edit_distance::weighted_levenshtein("abcde", "abc", 1, 0, 1), 0);
+/// ```
+pub fn weighted_levenshtein(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    ins: usize,
+    del: usize,
+    sub: usize,
+) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    weighted_levenshtein_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+        ins,
+        del,
+        sub,
+    )
+}
+
+fn weighted_levenshtein_chars(s: &[char], t: &[char], ins: usize, del: usize, sub: usize) -> usize {
+    levenshtein_generic(s, t, ins, del, |_, _| sub)
+}
+
+/// Levenshtein distance where the cost of substituting one character for another is decided by
+/// `sub_cost`. It is only called for characters that differ, returning 0 treats them as equal.
+/// Inserting and deleting still cost 1.
+///
+/// # Examples
+///
+/// ```
+/// let lookalikes = |a: char, b: char| match (a.min(b), a.max(b)) {
+///     ('0', 'O') | ('1', 'l') => 0,
+///     _ => 1,
+/// };
+/// assert_eq!(k_edit_distance::levenshtein_with("C0lOR", "COlOR", lookalikes), 0);
+/// assert_eq!(k_edit_distance::levenshtein_with("C0lOR", "CXlOR", lookalikes), 1);
+/// ```
+pub fn levenshtein_with<F: Fn(char, char) -> usize>(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    sub_cost: F,
+) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_generic(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+        1,
+        1,
+        |a, b| sub_cost(*a, *b),
+    )
+}
+
+/// Levenshtein distance where substituting characters of the same class costs `same_class` and
+/// of different classes costs `cross_class`, inserting and deleting cost 1.0. The classes are
+/// checked in this order, so each `char` is in exactly one:
+///
+/// 1. letters, `char::is_alphabetic` (Hangul included)
+/// 2. numbers, `char::is_numeric`
+/// 3. whitespace, `char::is_whitespace`
+/// 4. everything else, like punctuation and symbols
+///
+/// # Examples
+///
+/// ```
+/// // a typo'd digit is cheaper than a letter where a digit should be
+/// assert_eq!(k_edit_distance::classed_levenshtein("abc1", "abc2", 0.5, 1.0), 0.5);
+/// assert_eq!(k_edit_distance::classed_levenshtein("abc1", "abcX", 0.5, 1.0), 1.0);
+/// ```
+pub fn classed_levenshtein(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    same_class: f32,
+    cross_class: f32,
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    fractional_levenshtein(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+        |a, b| {
+            if char_class(*a) == char_class(*b) {
+                same_class
+            } else {
+                cross_class
+            }
+        },
+    )
+}
+
+/// Like `classed_levenshtein` but with the Unicode script of each `char` (Latin, Hangul, Han
+/// and so on) instead of its class, so a Hangul syllable standing in for a kanji costs
+/// `cross_script` while a typo within the same alphabet costs `same_script`. Inserting and
+/// deleting cost 1.0.
+///
+/// Characters every script uses, like digits, punctuation and whitespace, and combining marks
+/// that take the script of what they're on count as the same script as anything.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::script_weighted_distance;
+///
+/// assert_eq!(script_weighted_distance("color", "colour", 0.5, 1.0), 1.0);
+/// assert_eq!(script_weighted_distance("cat", "cut", 0.5, 1.0), 0.5);
+/// assert_eq!(script_weighted_distance("国語", "국語", 0.5, 1.0), 1.0);
+/// ```
+pub fn script_weighted_distance(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    same_script: f32,
+    cross_script: f32,
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    fractional_levenshtein(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+        |a, b| {
+            let shared = [Script::Common, Script::Inherited];
+            let (a, b) = (a.script(), b.script());
+            if a == b || shared.contains(&a) || shared.contains(&b) {
+                same_script
+            } else {
+                cross_script
+            }
+        },
+    )
+}
+
+/// Which of the `classed_levenshtein` classes `c` is in
+fn char_class(c: char) -> u8 {
+    if c.is_alphabetic() {
+        0
+    } else if c.is_numeric() {
+        1
+    } else if c.is_whitespace() {
+        2
+    } else {
+        3
+    }
+}
+
+/// `levenshtein_generic` with fractional substitution costs, inserting and deleting cost 1.0.
+pub(crate) fn fractional_levenshtein<T: PartialEq, F: Fn(&T, &T) -> f32>(
+    s: &[T],
+    t: &[T],
+    sub_cost: F,
+) -> f32 {
+    levenshtein_generic(s, t, 1.0, 1.0, sub_cost)
+}
+
+/// A number `levenshtein_generic` can add up edit costs in. It's implemented for the built in
+/// integer and float types.
+pub trait Cost: Copy + PartialOrd + core::ops::Add<Output = Self> {
+    /// What two equal items cost to line up, and where every row and column starts
+    const ZERO: Self;
+}
+
+macro_rules! impl_cost {
+    ($($ty:ty => $zero:expr),*) => {
+        $(impl Cost for $ty {
+            const ZERO: Self = $zero;
+        })*
+    };
+}
+
+impl_cost!(
+    u8 => 0, u16 => 0, u32 => 0, u64 => 0, usize => 0,
+    i8 => 0, i16 => 0, i32 => 0, i64 => 0, isize => 0,
+    f32 => 0.0, f64 => 0.0
+);
+
+/// Levenshtein distance between any two sequences with the costs in whatever number type `C`
+/// is, so integer, fractional and signed costs can all share one DP. Inserting costs `ins` and
+/// deleting costs `del` from the point of view of turning `s` into `t`, substituting costs
+/// whatever `sub_cost` says. It is only called for items that differ, equal items cost
+/// `C::ZERO`.
+///
+/// Only the previous and current row are kept around so memory is O(n).
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::levenshtein_generic;
+///
+/// let s: Vec<char> = "kitten".chars().collect();
+/// let t: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(levenshtein_generic(&s, &t, 1, 1, |_, _| 1), 3);
+/// assert_eq!(levenshtein_generic(&s, &t, 1.0, 1.0, |_, _| 0.5), 2.0);
+/// ```
+pub fn levenshtein_generic<T: PartialEq, C: Cost, F: Fn(&T, &T) -> C>(
+    s: &[T],
+    t: &[T],
+    ins: C,
+    del: C,
+    sub_cost: F,
+) -> C {
+    levenshtein_in_rows(s, t, ins, del, sub_cost, &mut vec![], &mut vec![])
+}
+
+/// `levenshtein_generic` in rows the caller owns so they can be reused between calls, with `s`
+/// used as it's iterated over. When it returns `prev` is the last row of the DP, `prev[j]` is
+/// the distance between all of `s` and `t[..j]`.
+fn levenshtein_in_rows<T, C, F, I>(
+    s: I,
+    t: &[T],
+    ins: C,
+    del: C,
+    sub_cost: F,
+    prev: &mut Vec<C>,
+    curr: &mut Vec<C>,
+) -> C
+where
+    T: PartialEq,
+    C: Cost,
+    F: Fn(&T, &T) -> C,
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+{
+    let n = t.len();
+    prev.clear();
+    prev.push(C::ZERO);
+    for j in 0..n {
+        prev.push(prev[j] + ins);
+    }
+    curr.clear();
+    curr.resize(n + 1, C::ZERO);
+
+    for s_item in s {
+        next_row(prev, curr, s_item.borrow(), t, ins, del, &sub_cost);
+        core::mem::swap(prev, curr);
+    }
+
+    prev[n]
+}
+
+/// The step all the row by row Levenshtein DPs share: fills in `curr`, the row for one more
+/// item `s_item` of `s`, from `prev`, the row before it. Both rows are `t.len() + 1` long and
+/// the costs are the same as `levenshtein_generic`.
+fn next_row<T: PartialEq, C: Cost, F: Fn(&T, &T) -> C>(
+    prev: &[C],
+    curr: &mut [C],
+    s_item: &T,
+    t: &[T],
+    ins: C,
+    del: C,
+    sub_cost: &F,
+) {
+    let min = |a: C, b: C| if b < a { b } else { a };
+
+    curr[0] = prev[0] + del;
+    for j in 1..=t.len() {
+        let substitution_cost = if *s_item == t[j - 1] {
+            C::ZERO
+        } else {
+            sub_cost(s_item, &t[j - 1])
+        };
+
+        curr[j] = min(
+            min(prev[j] + del, curr[j - 1] + ins),
+            prev[j - 1] + substitution_cost,
+        );
+    }
+}
+
+/// Computes `levenshtein_distance` reusing the same buffers for every call, so hot loops don't
+/// allocate once the buffers are big enough for the longest string seen.
+///
+/// It needs `&mut self` to use the buffers so it isn't thread safe, use one per thread.
+///
+/// # Examples
+///
+/// ```
+/// let mut levenshtein = k_edit_distance::Levenshtein::new();
+/// assert_eq!(levenshtein.distance("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein.distance("book", "back"), 2);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Levenshtein {
+    t: Vec<char>,
+    prev: Vec<usize>,
+    curr: Vec<usize>,
+}
+
+impl Levenshtein {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distance(&mut self, s: &str, t: &str) -> usize {
+        self.t.clear();
+        self.t.extend(t.chars());
+        levenshtein_in_rows(
+            s.chars(),
+            &self.t,
+            1,
+            1,
+            |_, _| 1,
+            &mut self.prev,
+            &mut self.curr,
+        )
+    }
+}
+
+/// The Levenshtein distance from a query that's typed one `char` at a time to a fixed target,
+/// like a search box checking each keystroke. Adding or removing a `char` only computes one new
+/// row of the matrix, O(length of the target), instead of starting again.
+///
+/// # Examples
+///
+/// ```
+/// let mut typed = k_edit_distance::IncrementalLevenshtein::new("kitten");
+/// assert_eq!(typed.push('k'), 5);
+/// assert_eq!(typed.push('u'), 5);
+/// assert_eq!(typed.pop(), 5);
+/// assert_eq!(typed.push('i'), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalLevenshtein {
+    target: Vec<char>,
+    // One row per char of the query so far, rows[i][j] is the distance between the first i
+    // chars of the query and target[..j]
+    rows: Vec<Vec<usize>>,
+}
+
+impl IncrementalLevenshtein {
+    pub fn new(target: &str) -> Self {
+        let target: Vec<char> = target.chars().collect();
+        let first_row = (0..=target.len()).collect();
+        Self {
+            target,
+            rows: vec![first_row],
+        }
+    }
+
+    /// Adds `c` to the end of the query and returns the new distance
+    pub fn push(&mut self, c: char) -> usize {
+        let prev = &self.rows[self.rows.len() - 1];
+        let mut curr = vec![0; self.target.len() + 1];
+        next_row(prev, &mut curr, &c, &self.target, 1, 1, &|_, _| 1);
+
+        self.rows.push(curr);
+        self.distance()
+    }
+
+    /// Removes the last `char` of the query, if there is one, and returns the new distance
+    pub fn pop(&mut self) -> usize {
+        if self.rows.len() > 1 {
+            self.rows.pop();
+        }
+        self.distance()
+    }
+
+    /// The distance between the query so far and the target
+    pub fn distance(&self) -> usize {
+        self.rows[self.rows.len() - 1][self.target.len()]
+    }
+}
+
+/// Returns `Some(distance)` if the Levenshtein distance between `s` and `t` is at most `k` and
+/// `None` otherwise.
+///
+/// Only the cells within `k` of the diagonal are computed and it stops as soon as a whole row is
+/// over `k`, so this is much cheaper than `levenshtein_distance` when filtering lots of words
+/// with a small `k`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::levenshtein_within("kitten", "sitting", 3), Some(3));
+/// assert_eq!(k_edit_distance::levenshtein_within("kitten", "sitting", 2), None);
+/// ```
+pub fn levenshtein_within(s: impl AsRef<str>, t: impl AsRef<str>, k: usize) -> Option<usize> {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_within_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+        k,
+    )
+}
+
+/// `levenshtein_within` for strings that are already split into `char`s. Only the cells within
+/// `band` of the diagonal are filled in, which is all an edit script of `band` or fewer edits
+/// can reach, so `None` means the distance is definitely over `band`.
+///
+/// # Examples
+///
+/// ```
+/// let s: Vec<char> = "kitten".chars().collect();
+/// let t: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(k_edit_distance::levenshtein_banded(&s, &t, 3), Some(3));
+/// assert_eq!(k_edit_distance::levenshtein_banded(&s, &t, 2), None);
+/// ```
+pub fn levenshtein_banded(s: &[char], t: &[char], band: usize) -> Option<usize> {
+    levenshtein_within_chars(s, t, band)
+}
+
+/// The same as `levenshtein_distance` but found with `levenshtein_banded`, starting with a narrow
+/// band and doubling it until the distance fits. Strings that are nearly the same only need a
+/// few narrow passes, which is close to linear time instead of filling in the whole matrix.
+///
+/// # Examples
+///
+/// ```
+/// let s: Vec<char> = "the quick brown fox".chars().collect();
+/// let t: Vec<char> = "the quick brown box".chars().collect();
+/// assert_eq!(k_edit_distance::levenshtein_expanding(&s, &t), 1);
+/// ```
+pub fn levenshtein_expanding(s: &[char], t: &[char]) -> usize {
+    let mut band = s.len().abs_diff(t.len()).max(1);
+    loop {
+        if let Some(dist) = levenshtein_within_chars(s, t, band) {
+            return dist;
+        }
+        // Can't be more than the longer length so this always finishes
+        band *= 2;
+    }
+}
+
+fn levenshtein_within_chars(s: &[char], t: &[char], k: usize) -> Option<usize> {
+    let m = s.len();
+    let n = t.len();
+    if m.abs_diff(n) > k {
+        return None;
+    }
+
+    // Anything over k is as good as infinite
+    let inf = k + 1;
+    let mut prev: Vec<usize> = (0..=n).map(|j| j.min(inf)).collect();
+    let mut curr = vec![inf; n + 1];
+
+    for i in 1..=m {
+        let lo = i.saturating_sub(k).max(1);
+        let hi = (i + k).min(n);
+
+        // The cells just outside the band are read by this and the next row
+        curr[lo - 1] = if lo == 1 { i.min(inf) } else { inf };
+        if hi < n {
+            curr[hi + 1] = inf;
+        }
+
+        let mut row_min = curr[lo - 1];
+        for j in lo..=hi {
+            let substitution_cost = if s[i - 1] == t[j - 1] { 0 } else { 1 };
+
+            curr[j] = (prev[j] + 1)
+                .min(curr[j - 1] + 1)
+                .min(prev[j - 1] + substitution_cost)
+                .min(inf);
+            row_min = row_min.min(curr[j]);
+        }
+
+        if row_min > k {
+            return None;
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    Some(prev[n]).filter(|&dist| dist <= k)
+}
+
+/// The same as `levenshtein_distance` but using Myers' bit-parallel algorithm, which keeps a
+/// whole column of the matrix in the bits of a `u64` and updates it with a handful of word
+/// operations per character of `text`. This is a lot faster for short patterns checked against
+/// lots of strings. A `pattern` over 64 `char`s doesn't fit in one word so it's split into
+/// blocks of 64 with the carries passed from one block to the next.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::myers_distance("kitten", "sitting"), 3);
+/// assert_eq!(k_edit_distance::myers_distance("국어", "숙어"), 1);
+/// ```
+pub fn myers_distance(pattern: &str, text: &str) -> usize {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let m = pattern.len();
+    if m > 64 {
+        return myers_blocks(&pattern, &text);
+    }
+    if m == 0 {
+        return text.len();
+    }
+
+    // Which positions in the pattern each character is at
+    let mut peq: BTreeMap<char, u64> = BTreeMap::new();
+    for (i, &c) in pattern.iter().enumerate() {
+        *peq.entry(c).or_default() |= 1 << i;
+    }
+
+    // The vertical deltas of the current column are +1 where pv is set and -1 where mv is
+    let mut pv = u64::MAX >> (64 - m);
+    let mut mv = 0u64;
+    let last = 1 << (m - 1);
+    let mut score = m;
+
+    for c in &text {
+        let eq = peq.get(c).copied().unwrap_or(0);
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+
+        if ph & last != 0 {
+            score += 1;
+        } else if mh & last != 0 {
+            score -= 1;
+        }
+
+        // The top row goes up by one each character, which is the 1 shifted in
+        ph = (ph << 1) | 1;
+        mh <<= 1;
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+    }
+
+    score
+}
+
+/// `myers_distance` for a pattern split over several words. Each block works the same as the
+/// single word version except the change along its top edge comes from the block above instead
+/// of always being +1.
+fn myers_blocks(pattern: &[char], text: &[char]) -> usize {
+    let m = pattern.len();
+    let blocks = m.div_ceil(64);
+
+    let mut peq: BTreeMap<char, Vec<u64>> = BTreeMap::new();
+    for (i, &c) in pattern.iter().enumerate() {
+        peq.entry(c).or_insert_with(|| vec![0; blocks])[i / 64] |= 1 << (i % 64);
+    }
+    let no_match = vec![0; blocks];
+
+    // The bits past the end of the pattern in the last block never carry into anything below
+    // them so they can start as anything
+    let mut pv = vec![u64::MAX; blocks];
+    let mut mv = vec![0u64; blocks];
+    let last = 1 << ((m - 1) % 64);
+    let mut score = m;
+
+    for c in text {
+        let eq = peq.get(c).unwrap_or(&no_match);
+        // The top row goes up by one each character
+        let mut carry = 1;
+        for b in 0..blocks {
+            let high = if b == blocks - 1 { last } else { 1 << 63 };
+            carry = myers_block(&mut pv[b], &mut mv[b], eq[b], carry, high);
+        }
+        score = score.wrapping_add_signed(carry as isize);
+    }
+
+    score
+}
+
+/// Moves one block of `myers_blocks` along a character, `carry_in` is the change along the top
+/// of the block and the change at the `high` bit is returned
+fn myers_block(pv: &mut u64, mv: &mut u64, eq: u64, carry_in: i8, high: u64) -> i8 {
+    let carry_neg = (carry_in < 0) as u64;
+    let xv = eq | *mv;
+    let eq = eq | carry_neg;
+    let xh = ((eq & *pv).wrapping_add(*pv) ^ *pv) | eq;
+    let mut ph = *mv | !(xh | *pv);
+    let mut mh = *pv & xh;
+
+    let carry_out = if ph & high != 0 {
+        1
+    } else if mh & high != 0 {
+        -1
+    } else {
+        0
+    };
+
+    ph = (ph << 1) | (carry_in > 0) as u64;
+    mh = (mh << 1) | carry_neg;
+    *pv = mh | !(xv | ph);
+    *mv = ph & xv;
+
+    carry_out
+}
+
+/// Sift4, a fast approximation of edit distance for things like autocomplete where
+/// `levenshtein_distance` is too slow to run on every keystroke. This is the "simplest" version
+/// of the algorithm, it walks both strings once and when they stop matching looks up to
+/// `max_offset` characters ahead in each for where they start matching again.
+///
+/// It is only an approximation and can be higher or lower than the real edit distance, for
+/// example "abc" to "bca" is 2 edits but Sift4 gives 1.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::sift4("hello", "hello", 5), 0);
+/// assert_eq!(k_edit_distance::sift4("abc", "bca", 5), 1);
+/// assert_eq!(k_edit_distance::levenshtein_distance("abc", "bca"), 2);
+/// ```
+pub fn sift4(s: impl AsRef<str>, t: impl AsRef<str>, max_offset: usize) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+    if s.is_empty() || t.is_empty() {
+        return s.len().max(t.len());
+    }
+
+    let (mut c1, mut c2) = (0, 0);
+    // Longest common substring total and the current run
+    let (mut lcss, mut local_cs) = (0, 0);
+
+    while c1 < s.len() && c2 < t.len() {
+        if s[c1] == t[c2] {
+            local_cs += 1;
+        } else {
+            lcss += local_cs;
+            local_cs = 0;
+            if c1 != c2 {
+                c1 = c1.max(c2);
+                c2 = c1;
+            }
+            for i in 0..max_offset {
+                if c1 + i >= s.len() && c2 + i >= t.len() {
+                    break;
+                }
+                if s.get(c1 + i).is_some_and(|c| Some(c) == t.get(c2)) {
+                    c1 += i;
+                    local_cs += 1;
+                    break;
+                }
+                if t.get(c2 + i).is_some_and(|c| Some(c) == s.get(c1)) {
+                    c2 += i;
+                    local_cs += 1;
+                    break;
+                }
+            }
+        }
+        c1 += 1;
+        c2 += 1;
+    }
+    lcss += local_cs;
+
+    s.len().max(t.len()) - lcss
+}
+
+/// Returned by `hamming_distance` when the two strings aren't the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LengthMismatch {
+    pub s_len: usize,
+    pub t_len: usize,
+}
+
+impl core::fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "strings have different lengths ({} and {} chars)",
+            self.s_len, self.t_len
+        )
+    }
+}
+
+impl core::error::Error for LengthMismatch {}
+
+/// Number of positions where `s` and `t` have a different character. Like `levenshtein_distance`
+/// this works on `char`s, not graphemes, so both strings need the same number of `char`s.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::hamming_distance("karolin", "kathrin"), Ok(3));
+/// assert!(k_edit_distance::hamming_distance("karolin", "kath").is_err());
+/// ```
+pub fn hamming_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> Result<usize, LengthMismatch> {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    hamming_distance_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+    )
+}
+
+fn hamming_distance_chars(s: &[char], t: &[char]) -> Result<usize, LengthMismatch> {
+    if s.len() != t.len() {
+        return Err(LengthMismatch {
+            s_len: s.len(),
+            t_len: t.len(),
+        });
+    }
+
+    Ok(s.iter().zip(t).filter(|(a, b)| a != b).count())
+}
+
+/// Jaro similarity, 1.0 for exactly the same and 0.0 for nothing in common. Works better than
+/// edit distance for short strings like names.
+///
+/// # Examples
+///
+/// ```
+/// let similarity = k_edit_distance::jaro_similarity("MARTHA", "MARHTA");
+/// assert!((similarity - 0.944).abs() < 0.001);
+/// ```
+pub fn jaro_similarity(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    jaro_similarity_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+    )
+}
+
+fn jaro_similarity_chars(s: &[char], t: &[char]) -> f32 {
+    if s.is_empty() && t.is_empty() {
+        return 1.;
+    }
+    if s.is_empty() || t.is_empty() {
+        return 0.;
+    }
+
+    // Characters only count as matching if they are this close to each other
+    let match_distance = (s.len().max(t.len()) / 2).saturating_sub(1);
+    let mut s_matched = vec![false; s.len()];
+    let mut t_matched = vec![false; t.len()];
+
+    let mut matches = 0;
+    for (i, s_char) in s.iter().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(t.len());
+        for j in start..end {
+            if !t_matched[j] && *s_char == t[j] {
+                s_matched[i] = true;
+                t_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.;
+    }
+
+    // Matched characters that are in a different order
+    let s_matches = s.iter().zip(&s_matched).filter(|(_, m)| **m);
+    let t_matches = t.iter().zip(&t_matched).filter(|(_, m)| **m);
+    let half_transpositions = s_matches
+        .zip(t_matches)
+        .filter(|((a, _), (b, _))| a != b)
+        .count();
+
+    let matches = matches as f32;
+    let transpositions = (half_transpositions / 2) as f32;
+    (matches / s.len() as f32 + matches / t.len() as f32 + (matches - transpositions) / matches)
+        / 3.
+}
+
+/// Jaro-Winkler similarity, `jaro_similarity` boosted for strings that start the same. Uses the
+/// standard scaling factor of 0.1 for up to 4 leading characters.
+///
+/// # Examples
+///
+/// ```
+/// let similarity = k_edit_distance::jaro_winkler_similarity("MARTHA", "MARHTA");
+/// assert!((similarity - 0.961).abs() < 0.001);
+/// ```
+pub fn jaro_winkler_similarity(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s = s.chars().collect::<Vec<_>>();
+    let t = t.chars().collect::<Vec<_>>();
+
+    let jaro = jaro_similarity_chars(&s, &t);
+    let prefix = s.iter().zip(&t).take(4).take_while(|(a, b)| a == b).count();
+
+    jaro + prefix as f32 * 0.1 * (1. - jaro)
+}
+
+/// Like `levenshtein_distance` but swapping two adjacent characters only counts as one edit, so
+/// "teh" to "the" is 1 instead of 2.
+///
+/// This is the unrestricted Damerau-Levenshtein distance, characters can still be edited after
+/// they have been transposed.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::damerau_levenshtein_distance("ca", "ac"), 1);
+/// assert_eq!(k_edit_distance::levenshtein_distance("ca", "ac"), 2);
+/// ```
+pub fn damerau_levenshtein_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    damerau_levenshtein_distance_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+    )
+}
+
+fn damerau_levenshtein_distance_chars(s: &[char], t: &[char]) -> usize {
+    damerau_levenshtein_slice(s, t)
+}
+
+/// `damerau_levenshtein_distance` between any two sequences, like `levenshtein_slice`. The
+/// elements need to be `Ord` to remember where each one was last seen.
+///
+/// # Examples
+///
+/// ```
+/// let s = ["I", "saw", "her"];
+/// let t = ["saw", "I", "her"];
+/// assert_eq!(k_edit_distance::damerau_levenshtein_slice(&s, &t), 1);
+/// assert_eq!(k_edit_distance::levenshtein_slice(&s, &t), 2);
+/// ```
+pub fn damerau_levenshtein_slice<T: Ord>(s: &[T], t: &[T]) -> usize {
+    let m = s.len();
+    let n = t.len();
+    let max_dist = m + n;
+    // d is shifted by one so row/column 0 can hold the "infinite" border, which keeps the
+    // transposition lookup below in bounds when there is nothing to transpose with.
+    let mut d = vec![vec![0; n + 2]; m + 2];
+    // Last row each element was seen on in s
+    let mut last_row: BTreeMap<&T, usize> = BTreeMap::new();
+
+    d[0][0] = max_dist;
+    for i in 0..=m {
+        d[i + 1][0] = max_dist;
+        d[i + 1][1] = i;
+    }
+    for j in 0..=n {
+        d[0][j + 1] = max_dist;
+        d[1][j + 1] = j;
+    }
+
+    for i in 1..=m {
+        // Last column in this row where s[i - 1] matched
+        let mut last_match_col = 0;
+        for j in 1..=n {
+            let k = *last_row.get(&&t[j - 1]).unwrap_or(&0);
+            let l = last_match_col;
+            let substitution_cost = if s[i - 1] == t[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+
+            d[i + 1][j + 1] = (d[i][j] + substitution_cost)
+                .min(d[i + 1][j] + 1)
+                .min(d[i][j + 1] + 1)
+                .min(d[k][l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(&s[i - 1], i);
+    }
+
+    d[m + 1][n + 1]
+}
+
+/// Optimal string alignment distance, the restricted form of `damerau_levenshtein_distance`.
+///
+/// Adjacent transpositions count as one edit, but no substring can be edited more than once. So
+/// "ca" to "abc" is 3 here (the "ca" can't be swapped to "ac" and then have a "b" inserted
+/// between them) while the full Damerau-Levenshtein distance is 2.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::osa_distance("ca", "ac"), 1);
+/// assert_eq!(k_edit_distance::osa_distance("ca", "abc"), 3);
+/// assert_eq!(k_edit_distance::damerau_levenshtein_distance("ca", "abc"), 2);
+/// ```
+pub fn osa_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    osa_distance_chars(
+        &s.chars().collect::<Vec<_>>(),
+        &t.chars().collect::<Vec<_>>(),
+    )
+}
+
+fn osa_distance_chars(s: &[char], t: &[char]) -> usize {
+    let m = s.len();
+    let n = t.len();
+    let mut d = vec![vec![0; n + 1]; m + 1];
+
+    for (i, row) in d.iter_mut().enumerate().skip(1) {
+        row[0] = i;
+    }
+
+    for (j, cell) in d[0].iter_mut().enumerate().skip(1) {
+        *cell = j;
+    }
+
+    for j in 1..=n {
+        for i in 1..=m {
+            let substitution_cost = if s[i - 1] == t[j - 1] { 0 } else { 1 };
+
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + substitution_cost);
+
+            if i > 1 && j > 1 && s[i - 1] == t[j - 2] && s[i - 2] == t[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[m][n]
+}
+
+/// Options for `k_edit_distance_with`, the default is what `k_edit_distance` uses.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct KEditConfig {
+    /// Fold together the consonants in `consonant_groups`. When this is off aspirated and tense
+    /// consonants count as different to their plain form.
+    pub consonant_normalization: bool,
+    /// Which consonants `consonant_normalization` folds together, the paper's groups listed on
+    /// `normalize` by default.
+    pub consonant_groups: ConsonantGroups,
+    /// Fold together the near homophone vowels in `VOWEL_GROUPS`, useful for casual or dialectal
+    /// spellings. Off by default since the paper doesn't do this.
+    pub vowel_normalization: bool,
+    /// Fold together the bigger set of vowels in `EXTENDED_VOWEL_GROUPS`, which are only near
+    /// homophones in casual speech. They include `VOWEL_GROUPS` so this works with or without
+    /// `vowel_normalization`. Off by default.
+    pub extended_vowel_normalization: bool,
+    /// Which Unicode normalization form each syllable is put in before anything is folded.
+    pub normalization_form: NormalizationForm,
+    /// Drop all whitespace (anything `char::is_whitespace`) so "국 어" and "국어" are the same.
+    pub strip_whitespace: bool,
+    /// Spell neighbouring syllables the way ㅎ aspirates the consonant next to it before
+    /// anything else, so 좋다 and 조타 or 축하 and 추카 are the same. This covers a final ㅎ
+    /// (alone or in ㄶ ㅀ) before ㄱ ㄷ ㅈ and a final ㄱ ㄷ ㅂ ㅈ ㅅ ㄺ ㄼ before ㅎ. Off by
+    /// default since it compares pronunciation rather than spelling.
+    pub aspiration: bool,
+    /// Split the double final consonants (겹받침) like ㄺ into the two consonants they're made
+    /// of, so 닭 compares the same as its parts ㄷㅏㄹㄱ. A syllable with one then has four jamo
+    /// instead of three, set `syllable_weight` to 4 to keep scores from going over 1.0. Off by
+    /// default since the paper counts them as one jamo.
+    pub split_double_finals: bool,
+    /// Treat half and full width characters as their normal width, like
+    /// `LevenshteinConfig::fold_width`. Half width Hangul jamo become compatibility jamo.
+    pub fold_width: bool,
+    /// What each syllable of the longer string adds to the denominator, 3 since a Hangul
+    /// syllable has at most three jamo. A score can only go over 1.0 if some syllable
+    /// normalizes to more jamo than this, which `split_double_finals` or a grapheme with
+    /// combining marks can do. 0 makes every score 0.0.
+    pub syllable_weight: usize,
+    /// Add `length_penalty * |len(s) - len(t)| / max_len` to the score, counting syllables
+    /// like the denominator does, so strings of very different lengths are further apart than
+    /// their edit distance says. The score still doesn't go over 1.0 from this. 0.0 by default
+    /// which changes nothing.
+    pub length_penalty: f32,
+    /// How much less each syllable counts than the one before it, so a difference at the start
+    /// of a word matters more than the same difference at the end. The syllables at position
+    /// `i` are weighted `1 / (1 + position_decay * i)`, both in the edit distance and in the
+    /// denominator so scores stay within 0.0 to 1.0. 0.0 by default which weights every
+    /// syllable the same. Only the score uses this, `k_edit_distance_raw` is still unweighted.
+    pub position_decay: f32,
+}
+
+impl Default for KEditConfig {
+    fn default() -> Self {
+        Self {
+            consonant_normalization: true,
+            consonant_groups: ConsonantGroups::default(),
+            vowel_normalization: false,
+            extended_vowel_normalization: false,
+            normalization_form: NormalizationForm::Nfd,
+            strip_whitespace: true,
+            aspiration: false,
+            split_double_finals: false,
+            fold_width: false,
+            syllable_weight: 3,
+            length_penalty: 0.,
+            position_decay: 0.,
+        }
+    }
+}
+
+/// Builds up a `KEditConfig` one option at a time, for when setting several of them in a struct
+/// literal gets unwieldy. It starts from the default config.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::KEditBuilder;
+///
+/// let distance = KEditBuilder::new()
+///     .vowel_normalization(true)
+///     .aspiration(true)
+///     .distance("좋대", "조테");
+/// assert_eq!(distance, 0.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KEditBuilder {
+    config: KEditConfig,
+}
+
+impl KEditBuilder {
+    /// Starts from `KEditConfig::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `KEditConfig::consonant_normalization`
+    pub fn consonant_normalization(mut self, consonant_normalization: bool) -> Self {
+        self.config.consonant_normalization = consonant_normalization;
+        self
+    }
+
+    /// Sets `KEditConfig::consonant_groups`
+    pub fn consonant_groups(mut self, consonant_groups: ConsonantGroups) -> Self {
+        self.config.consonant_groups = consonant_groups;
+        self
+    }
+
+    /// Sets `KEditConfig::vowel_normalization`
+    pub fn vowel_normalization(mut self, vowel_normalization: bool) -> Self {
+        self.config.vowel_normalization = vowel_normalization;
+        self
+    }
+
+    /// Sets `KEditConfig::extended_vowel_normalization`
+    pub fn extended_vowel_normalization(mut self, extended_vowel_normalization: bool) -> Self {
+        self.config.extended_vowel_normalization = extended_vowel_normalization;
+        self
+    }
+
+    /// Sets `KEditConfig::normalization_form`
+    pub fn normalization_form(mut self, normalization_form: NormalizationForm) -> Self {
+        self.config.normalization_form = normalization_form;
+        self
+    }
+
+    /// Sets `KEditConfig::strip_whitespace`
+    pub fn strip_whitespace(mut self, strip_whitespace: bool) -> Self {
+        self.config.strip_whitespace = strip_whitespace;
+        self
+    }
+
+    /// Sets `KEditConfig::aspiration`
+    pub fn aspiration(mut self, aspiration: bool) -> Self {
+        self.config.aspiration = aspiration;
+        self
+    }
+
+    /// Sets `KEditConfig::split_double_finals`
+    pub fn split_double_finals(mut self, split_double_finals: bool) -> Self {
+        self.config.split_double_finals = split_double_finals;
+        self
+    }
+
+    /// Sets `KEditConfig::fold_width`
+    pub fn fold_width(mut self, fold_width: bool) -> Self {
+        self.config.fold_width = fold_width;
+        self
+    }
+
+    /// Sets `KEditConfig::syllable_weight`
+    pub fn syllable_weight(mut self, syllable_weight: usize) -> Self {
+        self.config.syllable_weight = syllable_weight;
+        self
+    }
+
+    /// Sets `KEditConfig::length_penalty`
+    pub fn length_penalty(mut self, length_penalty: f32) -> Self {
+        self.config.length_penalty = length_penalty;
+        self
+    }
+
+    /// Sets `KEditConfig::position_decay`
+    pub fn position_decay(mut self, position_decay: f32) -> Self {
+        self.config.position_decay = position_decay;
+        self
+    }
+
+    /// `k_edit_distance_with` using the options set so far
+    pub fn distance(&self, s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+        k_edit_distance_with(s, t, &self.config)
+    }
+
+    /// The config with the options set so far
+    pub fn build(self) -> KEditConfig {
+        self.config
+    }
+}
+
+/// The Unicode normalization forms from `unicode_normalization`, see `KEditConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NormalizationForm {
+    /// Canonical composition, Hangul syllables stay whole so nothing inside them is folded.
+    Nfc,
+    /// Canonical decomposition, splits Hangul syllables into jamo. This is what the paper does.
+    #[default]
+    Nfd,
+    /// Compatibility composition, like `Nfc` but also turns compatibility characters like
+    /// full width or circled letters into their plain form.
+    Nfkc,
+    /// Compatibility decomposition, like `Nfd` but also turns compatibility characters into
+    /// their plain form.
+    Nfkd,
+}
+
+/// The vowels folded together when `KEditConfig::vowel_normalization` is on, each group is
+/// folded to its first member. They are written as compatibility jamo but the matching
+/// conjoining jamo from NFD are folded the same way.
+pub const VOWEL_GROUPS: &[&[char]] = &[&['ㅐ', 'ㅔ'], &['ㅚ', 'ㅙ', 'ㅞ']];
+
+/// The vowels folded together when `KEditConfig::extended_vowel_normalization` is on, written
+/// and folded the same way as `VOWEL_GROUPS`. On top of those it has the groups that get mixed
+/// up in casual writing even though they're said differently when said carefully:
+///
+/// - ㅐ, ㅔ and ㅚ, ㅙ, ㅞ from `VOWEL_GROUPS`
+/// - ㅒ, ㅖ, the y versions of ㅐ and ㅔ, like 얘기 and 예기
+/// - ㅘ, ㅝ, the w vowels
+pub const EXTENDED_VOWEL_GROUPS: &[&[char]] = &[
+    &['ㅐ', 'ㅔ'],
+    &['ㅚ', 'ㅙ', 'ㅞ'],
+    &['ㅒ', 'ㅖ'],
+    &['ㅘ', 'ㅝ'],
+];
+
+/// Splits `s` into jamo with NFD and folds together consonants that the Kang Seung Shik method
+/// treats as the same, dropping any whitespace. This is what `k_edit_distance` compares.
+///
+/// The consonant groups (`CONSONANT_GROUPS`) are folded to their first member:
+///
+/// - ㄱ, ㅋ, ㄲ
+/// - ㄷ, ㄸ, ㅌ
+/// - ㅂ, ㅃ, ㅍ
+/// - ㅅ, ㅆ
+/// - ㅈ, ㅉ, ㅊ
+///
+/// Syllables come out as conjoining jamo (U+1100 to U+11FF) since that is what NFD produces,
+/// the groups are applied to both leading and trailing consonants. Compatibility jamo like "ㅋ"
+/// written on their own (U+3131 to U+3163) become conjoining jamo too, leading consonants
+/// unless they can only be a final like ㄳ, so "ㄱ" is the same as the ᄀ in "가". Each is its own
+/// grapheme so it counts as a syllable of one jamo in `k_edit_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::normalize;
+///
+/// assert_eq!(normalize("국"), vec!['\u{1100}', '\u{116E}', '\u{11A8}']);
+/// // ㅋ is folded to ㄱ in both positions
+/// assert_eq!(normalize("쿸"), normalize("국"));
+/// assert_eq!(normalize("국 어"), normalize("국어"));
+/// ```
+pub fn normalize(s: &str) -> Vec<char> {
+    normalize_with(s, &KEditConfig::default())
+}
+
+/// `normalize` with the options in `config`, this is what `k_edit_distance_with` compares.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{normalize_with, KEditConfig};
+///
+/// let config = KEditConfig {
+///     consonant_normalization: false,
+///     ..Default::default()
+/// };
+/// assert_ne!(normalize_with("쿡", &config), normalize_with("국", &config));
+/// ```
+pub fn normalize_with(s: &str, config: &KEditConfig) -> Vec<char> {
+    let mut normalized = vec![];
+
+    for unicode_char in config_graphemes(s, config) {
+        normalize_grapheme(&mut normalized, &unicode_char, config);
+    }
+
+    normalized
+}
+
+/// `normalize` joined back into a `String`, for logging or storing the form `k_edit_distance`
+/// compares. The jamo are conjoining jamo so most fonts show them put back together as
+/// syllables, the string is still made of the jamo.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::to_jamo_string;
+///
+/// assert_eq!(to_jamo_string("국어"), "\u{1100}\u{116E}\u{11A8}\u{110B}\u{1165}");
+/// assert_eq!(to_jamo_string("쿸 어"), to_jamo_string("국어"));
+/// ```
+pub fn to_jamo_string(s: &str) -> String {
+    to_jamo_string_with(s, &KEditConfig::default())
+}
+
+/// `to_jamo_string` with the options in `config`, `normalize_with` as a `String`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{to_jamo_string_with, KEditConfig};
+///
+/// let config = KEditConfig {
+///     consonant_normalization: false,
+///     ..Default::default()
+/// };
+/// assert_eq!(to_jamo_string_with("쿡", &config), "\u{110F}\u{116E}\u{11A8}");
+/// ```
+pub fn to_jamo_string_with(s: &str, config: &KEditConfig) -> String {
+    normalize_with(s, config).into_iter().collect()
+}
+
+/// The graphemes of `s`, respelled by `aspiration` if `config` turns it on
+fn config_graphemes<'a>(s: &'a str, config: &KEditConfig) -> Vec<Cow<'a, str>> {
+    let graphemes: Vec<&str> = s.graphemes(true).collect();
+    if !config.aspiration {
+        return graphemes.into_iter().map(Cow::Borrowed).collect();
+    }
+    hangul::aspirate(&graphemes)
+        .into_iter()
+        .map(Cow::Owned)
+        .collect()
+}
+
+fn normalize_grapheme(normalized: &mut Vec<char>, unicode_char: &str, config: &KEditConfig) {
+    let folded;
+    let unicode_char = if config.fold_width {
+        folded = fold_width(unicode_char);
+        &folded
+    } else {
+        unicode_char
+    };
+
+    match config.normalization_form {
+        NormalizationForm::Nfc => fold_into(normalized, unicode_char.nfc(), config),
+        NormalizationForm::Nfd => fold_into(normalized, unicode_char.nfd(), config),
+        NormalizationForm::Nfkc => fold_into(normalized, unicode_char.nfkc(), config),
+        NormalizationForm::Nfkd => fold_into(normalized, unicode_char.nfkd(), config),
+    }
+}
+
+fn fold_into(normalized: &mut Vec<char>, chars: impl Iterator<Item = char>, config: &KEditConfig) {
+    for char in chars {
+        if config.strip_whitespace && char.is_whitespace() {
+            continue;
+        }
+        // So a jamo typed on its own compares the same as the one in a syllable
+        let char = hangul::conjoining_jamo(char);
+        match hangul::split_double_final(char).filter(|_| config.split_double_finals) {
+            Some((first, second)) => {
+                normalized.push(fold_char(first, config));
+                normalized.push(fold_char(second, config));
+            }
+            None => normalized.push(fold_char(char, config)),
+        }
+    }
+}
+
+fn fold_char(char: char, config: &KEditConfig) -> char {
+    let mut char = char;
+    if config.consonant_normalization {
+        char = config.consonant_groups.fold(char);
+    }
+    if config.extended_vowel_normalization {
+        char = fold_vowel(char, EXTENDED_VOWEL_GROUPS);
+    } else if config.vowel_normalization {
+        char = fold_vowel(char, VOWEL_GROUPS);
+    }
+    char
+}
+
+fn fold_vowel(char: char, groups: &[&[char]]) -> char {
+    // The conjoining vowels are in the same order as the compatibility ones
+    const CONJOINING_START: u32 = 0x1161;
+    const COMPATIBILITY_START: u32 = 0x314F;
+    let conjoining = ('\u{1161}'..='\u{1175}').contains(&char);
+    let compatibility = if conjoining {
+        char::from_u32(char as u32 - CONJOINING_START + COMPATIBILITY_START).unwrap()
+    } else {
+        char
+    };
+
+    match groups.iter().find(|group| group.contains(&compatibility)) {
+        Some(group) if conjoining => {
+            char::from_u32(group[0] as u32 - COMPATIBILITY_START + CONJOINING_START).unwrap()
+        }
+        Some(group) => group[0],
+        None => char,
+    }
+}
+
+/// Edit distance over whole syllables, where each syllable is its normalized jamo. Substituting
+/// one syllable for another costs the jamo edit distance between them and inserting or deleting
+/// a syllable costs all of its jamo. This lets a dropped or added syllable shift the rest of the
+/// word without every following syllable counting as different.
+fn syllable_matrix(s: &[Vec<char>], t: &[Vec<char>]) -> Vec<Vec<usize>> {
+    let m = s.len();
+    let n = t.len();
+    let mut d = vec![vec![0; n + 1]; m + 1];
+
+    for i in 1..=m {
+        d[i][0] = d[i - 1][0] + s[i - 1].len();
+    }
+
+    for j in 1..=n {
+        d[0][j] = d[0][j - 1] + t[j - 1].len();
+    }
+
+    for i in 1..=m {
+        for j in 1..=n {
+            let syllable_dist = levenshtein_distance_chars(&s[i - 1], &t[j - 1]);
+
+            d[i][j] = (d[i - 1][j] + s[i - 1].len())
+                .min(d[i][j - 1] + t[j - 1].len())
+                .min(d[i - 1][j - 1] + syllable_dist);
+        }
+    }
+
+    d
+}
+
+/// Which syllables `syllable_matrix` lined up with each other, from the start of both strings.
+/// Each entry is the index of the syllable in `s` and `t` (`None` for an inserted or deleted
+/// syllable) and how much it cost.
+fn syllable_alignment(
+    s: &[Vec<char>],
+    t: &[Vec<char>],
+) -> Vec<(Option<usize>, Option<usize>, usize)> {
+    let d = syllable_matrix(s, t);
+    let mut alignment = vec![];
+
+    let (mut i, mut j) = (s.len(), t.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let syllable_dist = levenshtein_distance_chars(&s[i - 1], &t[j - 1]);
+            if d[i][j] == d[i - 1][j - 1] + syllable_dist {
+                alignment.push((Some(i - 1), Some(j - 1), syllable_dist));
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && d[i][j] == d[i - 1][j] + s[i - 1].len() {
+            alignment.push((Some(i - 1), None, s[i - 1].len()));
+            i -= 1;
+        } else {
+            alignment.push((None, Some(j - 1), t[j - 1].len()));
+            j -= 1;
+        }
+    }
+
+    alignment.reverse();
+    alignment
+}
+
+/// Returns 1.0 for most different and 0.0 for exactly the same.
+/// Implementation of "Word Similarity Calculation by Using the Edit Distance Metrics with Consonant Normalization" https://web.archive.org/web/20260112025218/https://koreascience.kr/article/JAKO201502152089381.pdf from Kang Seung Shik I'm not sure if it's 100% correct.
+///
+/// # Examples
+///
+/// ```
+/// let distance = k_edit_distance::k_edit_distance("국어", "숙어");
+/// assert_eq!(distance, 0.16666667);
+/// let distance = k_edit_distance::k_edit_distance("신문", "신문");
+/// assert_eq!(distance, 0.0);
+/// let distance = k_edit_distance::k_edit_distance("하늘", "택시");
+/// assert_eq!(distance, 1.0);
+/// ```
+///
+/// It's symmetric, `k_edit_distance(s, t)` is always the same as `k_edit_distance(t, s)`, and
+/// so is `k_edit_distance_with` for any config.
+///
+/// Whitespace is stripped before comparing so it doesn't count towards either string's length,
+/// and two strings that are empty (or only whitespace) are 0.0. Anything else that is its own
+/// grapheme, like a combining mark with nothing to combine with, counts as a syllable.
+pub fn k_edit_distance(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    k_edit_distance_with(s, t, &KEditConfig::default())
+}
+
+/// `k_edit_distance` with the options in `config`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{k_edit_distance_with, KEditConfig};
+///
+/// let config = KEditConfig {
+///     consonant_normalization: false,
+///     ..Default::default()
+/// };
+/// assert_eq!(k_edit_distance_with("국어", "쿡어", &config), 0.16666667);
+/// assert_eq!(k_edit_distance::k_edit_distance("국어", "쿡어"), 0.0);
+/// ```
+pub fn k_edit_distance_with(s: impl AsRef<str>, t: impl AsRef<str>, config: &KEditConfig) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    k_edit_distance_with_f64(s, t, config) as f32
+}
+
+/// `k_edit_distance` divided in `f64`, for when you're adding up or averaging lots of scores
+/// and the `f32` rounding would add up. The `f32` version is this cast down so the two always
+/// agree to `f32` precision.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::k_edit_distance_f64("국어", "숙어"), 1.0 / 6.0);
+/// assert_eq!(k_edit_distance::k_edit_distance("국어", "숙어"), 0.16666667);
+/// ```
+pub fn k_edit_distance_f64(s: impl AsRef<str>, t: impl AsRef<str>) -> f64 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    k_edit_distance_with_f64(s, t, &KEditConfig::default())
+}
+
+/// The lowest `k_edit_distance_with` score out of every config in `configs`, for trying strict
+/// and loose normalization and going with whichever says the strings are closer. No configs at
+/// all is the same as `k_edit_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{k_edit_distance_best, KEditConfig};
+///
+/// let strict = KEditConfig {
+///     consonant_normalization: false,
+///     ..Default::default()
+/// };
+/// let loose = KEditConfig {
+///     vowel_normalization: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(k_edit_distance_best("게", "캐", &[strict, loose]), 0.0);
+/// ```
+pub fn k_edit_distance_best(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    configs: &[KEditConfig],
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    if configs.is_empty() {
+        return k_edit_distance(s, t);
+    }
+    configs
+        .iter()
+        .map(|config| k_edit_distance_with(s, t, config))
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn k_edit_distance_with_f64(s: &str, t: &str, config: &KEditConfig) -> f64 {
+    let (edit_distance, max, s_len, t_len) = if config.position_decay == 0. {
+        let (edit_distance, s_len, t_len) = k_edit_distance_parts(s, t, config);
+        let max = config.syllable_weight * s_len.max(t_len);
+        (edit_distance as f64, max as f64, s_len, t_len)
+    } else {
+        let s_norm = NormalizedSyllables::new(s, config);
+        let t_norm = NormalizedSyllables::new(t, config);
+        let decay = config.position_decay as f64;
+        let max = (0..s_norm.len().max(t_norm.len()))
+            .map(|i| config.syllable_weight as f64 * position_weight(i, decay))
+            .sum();
+        (
+            weighted_syllable_distance(&s_norm, &t_norm, decay),
+            max,
+            s_norm.len(),
+            t_norm.len(),
+        )
+    };
+    // Nothing left to compare on either side
+    if max == 0. {
+        return 0.;
+    }
+    let n = edit_distance / max;
+
+    if config.length_penalty == 0. {
+        return n;
+    }
+    (n + length_penalty_for(s_len, t_len, config.length_penalty)).min(n.max(1.))
+}
+
+/// The integer `(edit_distance, max)` that `k_edit_distance` divides to get its score, for
+/// doing your own normalization or adding up lots of comparisons. `max` is 3 for each syllable
+/// of the longer string (see `KEditConfig::syllable_weight`), not counting anything that
+/// normalizes to nothing like stripped whitespace. Two strings with nothing left give `(0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::k_edit_distance_raw("국어", "숙어"), (1, 6));
+/// assert_eq!(k_edit_distance::k_edit_distance_raw("", ""), (0, 0));
+/// ```
+pub fn k_edit_distance_raw(s: impl AsRef<str>, t: impl AsRef<str>) -> (usize, usize) {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    k_edit_distance_raw_with(s, t, &KEditConfig::default())
+}
+
+fn k_edit_distance_raw_with(s: &str, t: &str, config: &KEditConfig) -> (usize, usize) {
+    let (edit_distance, s_len, t_len) = k_edit_distance_parts(s, t, config);
+    (edit_distance, config.syllable_weight * s_len.max(t_len))
+}
+
+/// The syllable edit distance and how many syllables each string has
+fn k_edit_distance_parts(s: &str, t: &str, config: &KEditConfig) -> (usize, usize, usize) {
+    let s_norm = NormalizedSyllables::new(s, config);
+    let t_norm = NormalizedSyllables::new(t, config);
+
+    (
+        syllable_distance(&s_norm, &t_norm),
+        s_norm.len(),
+        t_norm.len(),
+    )
+}
+
+/// The same normalized syllables as `syllables` but with all the jamo in one `Vec`, so a
+/// string is two allocations instead of one for every syllable. This is what the score is
+/// computed from, `syllables` is for when the syllables themselves are needed.
+#[derive(Debug)]
+struct NormalizedSyllables {
+    jamo: Vec<char>,
+    /// Where each syllable ends in `jamo`
+    ends: Vec<usize>,
+}
+
+impl NormalizedSyllables {
+    fn new(s: &str, config: &KEditConfig) -> Self {
+        let mut syllables = Self {
+            jamo: Vec::with_capacity(s.len()),
+            ends: vec![],
+        };
+        if config.aspiration {
+            for part in config_graphemes(s, config) {
+                syllables.push(&part, config);
+            }
+        } else {
+            for grapheme in s.graphemes(true) {
+                syllables.push(grapheme, config);
+            }
+        }
+        syllables
+    }
+
+    fn push(&mut self, grapheme: &str, config: &KEditConfig) {
+        let start = self.jamo.len();
+        normalize_grapheme(&mut self.jamo, grapheme, config);
+        // Like in `syllables` anything that normalizes to nothing isn't a syllable
+        if self.jamo.len() > start {
+            self.ends.push(self.jamo.len());
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    fn get(&self, i: usize) -> &[char] {
+        let start = if i == 0 { 0 } else { self.ends[i - 1] };
+        &self.jamo[start..self.ends[i]]
+    }
+}
+
+/// The bottom right corner of `syllable_matrix` keeping only two rows of it
+fn syllable_distance(s: &NormalizedSyllables, t: &NormalizedSyllables) -> usize {
+    let n = t.len();
+    let mut prev = Vec::with_capacity(n + 1);
+    prev.push(0);
+    for j in 0..n {
+        prev.push(prev[j] + t.get(j).len());
+    }
+    let mut curr = vec![0; n + 1];
+
+    for i in 0..s.len() {
+        let s_syllable = s.get(i);
+        curr[0] = prev[0] + s_syllable.len();
+        for j in 1..=n {
+            let t_syllable = t.get(j - 1);
+            curr[j] = (prev[j] + s_syllable.len())
+                .min(curr[j - 1] + t_syllable.len())
+                .min(prev[j - 1] + jamo_distance(s_syllable, t_syllable));
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[n]
+}
+
+/// `syllable_distance` with every edit weighted by `position_weight` of the syllables it's at.
+/// Deleting or inserting a syllable counts at its own position and lining two up counts at
+/// whichever of them is further along, so swapping `s` and `t` gives the same distance.
+fn weighted_syllable_distance(s: &NormalizedSyllables, t: &NormalizedSyllables, decay: f64) -> f64 {
+    let n = t.len();
+    let mut prev = Vec::with_capacity(n + 1);
+    prev.push(0.);
+    for j in 0..n {
+        prev.push(prev[j] + t.get(j).len() as f64 * position_weight(j, decay));
+    }
+    let mut curr = vec![0.; n + 1];
+
+    for i in 0..s.len() {
+        let s_syllable = s.get(i);
+        let s_weight = position_weight(i, decay);
+        curr[0] = prev[0] + s_syllable.len() as f64 * s_weight;
+        for j in 1..=n {
+            let t_syllable = t.get(j - 1);
+            let deletion = prev[j] + s_syllable.len() as f64 * s_weight;
+            let insertion = curr[j - 1] + t_syllable.len() as f64 * position_weight(j - 1, decay);
+            let substitution = prev[j - 1]
+                + jamo_distance(s_syllable, t_syllable) as f64
+                    * position_weight(i.max(j - 1), decay);
+            curr[j] = deletion.min(insertion).min(substitution);
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[n]
+}
+
+/// How much the syllable at `position` counts for `KEditConfig::position_decay`
+fn position_weight(position: usize, decay: f64) -> f64 {
+    1. / (1. + decay * position as f64)
+}
+
+/// `levenshtein_distance_chars` between two syllables that keeps its rows on the stack when
+/// they're as short as syllables usually are
+fn jamo_distance(s: &[char], t: &[char]) -> usize {
+    const MAX: usize = 8;
+    if t.len() >= MAX {
+        return levenshtein_distance_chars(s, t);
+    }
+
+    let n = t.len();
+    let mut prev = [0; MAX];
+    for (j, cell) in prev.iter_mut().enumerate().take(n + 1) {
+        *cell = j;
+    }
+    let mut curr = [0; MAX];
+    for (i, s_char) in s.iter().enumerate() {
+        curr[0] = i + 1;
+        for j in 1..=n {
+            let substitution_cost = if *s_char == t[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1)
+                .min(curr[j - 1] + 1)
+                .min(prev[j - 1] + substitution_cost);
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[n]
+}
+
+/// Breaks `s` into syllables and normalizes each of them
+fn syllables<'a>(s: &'a str, config: &KEditConfig) -> (Vec<&'a str>, Vec<Vec<char>>) {
+    // Anything that normalizes to nothing, like whitespace that gets stripped, isn't a syllable
+    // so it doesn't make the strings look longer than they are
+    s.graphemes(true)
+        .zip(config_graphemes(s, config))
+        .map(|(syllable, part)| {
+            let mut normalized = vec![];
+            normalize_grapheme(&mut normalized, &part, config);
+            (syllable, normalized)
+        })
+        .filter(|(_, normalized)| !normalized.is_empty())
+        .unzip()
+}
+
+/// How `k_edit_distance` got its score, see `k_edit_distance_breakdown`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KBreakdown {
+    /// The summed jamo edit distance, the same as `k_edit_distance_raw(s, t).0`
+    pub raw_distance: usize,
+    /// What `raw_distance` is divided by, the same as `k_edit_distance_raw(s, t).1`
+    pub max: usize,
+    /// The same as `k_edit_distance(s, t)`
+    pub score: f32,
+    /// Each pair of syllables that were compared, in order
+    pub syllables: Vec<SyllablePair>,
+}
+
+/// Two syllables `k_edit_distance` lined up with each other. One side is `None` when the
+/// syllable was inserted or deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SyllablePair {
+    pub s: Option<String>,
+    pub t: Option<String>,
+    /// The jamo edit distance between the two syllables
+    pub distance: usize,
+}
+
+/// `k_edit_distance` along with which syllables were compared and how much each of them added
+/// to the distance, for debugging or showing where two words differ.
+///
+/// # Examples
+///
+/// ```
+/// let breakdown = k_edit_distance::k_edit_distance_breakdown("국어", "숙어");
+/// assert_eq!(breakdown.score, 0.16666667);
+/// assert_eq!(breakdown.syllables[0].s.as_deref(), Some("국"));
+/// assert_eq!(breakdown.syllables[0].t.as_deref(), Some("숙"));
+/// assert_eq!(breakdown.syllables[0].distance, 1);
+/// assert_eq!(breakdown.syllables[1].distance, 0);
+/// ```
+pub fn k_edit_distance_breakdown(s: impl AsRef<str>, t: impl AsRef<str>) -> KBreakdown {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let config = KEditConfig::default();
+    let (s_syllables, s_norm) = syllables(s, &config);
+    let (t_syllables, t_norm) = syllables(t, &config);
+
+    let syllables: Vec<_> = syllable_alignment(&s_norm, &t_norm)
+        .into_iter()
+        .map(|(i, j, distance)| SyllablePair {
+            s: i.map(|i| s_syllables[i].to_string()),
+            t: j.map(|j| t_syllables[j].to_string()),
+            distance,
+        })
+        .collect();
+
+    let (raw_distance, max) = k_edit_distance_raw_with(s, t, &config);
+    KBreakdown {
+        raw_distance,
+        max,
+        score: k_edit_distance_with(s, t, &config),
+        syllables,
+    }
+}
+
+/// What `k_edit_distance` worked with on the way to its score, see `k_edit_distance_traced`.
+/// It has the syllables, jamo and distances of each step as fields you can look at.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KTrace {
+    /// The syllables of `s` that were compared, whitespace stripped
+    pub s_syllables: Vec<String>,
+    /// The syllables of `t` that were compared, whitespace stripped
+    pub t_syllables: Vec<String>,
+    /// The normalized jamo of each syllable in `s_syllables`
+    pub s_jamo: Vec<Vec<char>>,
+    /// The normalized jamo of each syllable in `t_syllables`
+    pub t_jamo: Vec<Vec<char>>,
+    /// Each syllable that was compared, in order
+    pub steps: Vec<TraceStep>,
+    /// The same as `k_edit_distance_raw(s, t).0`
+    pub raw_distance: usize,
+    /// The same as `k_edit_distance_raw(s, t).1`
+    pub max: usize,
+}
+
+/// One syllable compared in a `KTrace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceStep {
+    /// The index into `KTrace::s_syllables`, `None` when the syllable was inserted
+    pub s_index: Option<usize>,
+    /// The index into `KTrace::t_syllables`, `None` when the syllable was deleted
+    pub t_index: Option<usize>,
+    /// The jamo edit distance for this step
+    pub distance: usize,
+    /// The distance of this step and every one before it
+    pub running_distance: usize,
+}
+
+/// `k_edit_distance` along with a `KTrace` of the syllables, normalized jamo and running
+/// distance it went through, so you can see what it did.
+///
+/// # Examples
+///
+/// ```
+/// let (score, trace) = k_edit_distance::k_edit_distance_traced("국어", "숙어");
+/// assert_eq!(score, 0.16666667);
+/// assert_eq!(trace.s_syllables, vec!["국", "어"]);
+/// assert_eq!(trace.s_jamo[0], vec!['ᄀ', 'ᅮ', 'ᆨ']);
+/// assert_eq!(trace.steps[1].running_distance, 1);
+/// ```
+pub fn k_edit_distance_traced(s: impl AsRef<str>, t: impl AsRef<str>) -> (f32, KTrace) {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let config = KEditConfig::default();
+    let (s_syllables, s_jamo) = syllables(s, &config);
+    let (t_syllables, t_jamo) = syllables(t, &config);
+
+    let mut running_distance = 0;
+    let steps = syllable_alignment(&s_jamo, &t_jamo)
+        .into_iter()
+        .map(|(s_index, t_index, distance)| {
+            running_distance += distance;
+            TraceStep {
+                s_index,
+                t_index,
+                distance,
+                running_distance,
+            }
+        })
+        .collect();
+
+    let (raw_distance, max) = k_edit_distance_raw_with(s, t, &config);
+    let trace = KTrace {
+        s_syllables: s_syllables.into_iter().map(String::from).collect(),
+        t_syllables: t_syllables.into_iter().map(String::from).collect(),
+        s_jamo,
+        t_jamo,
+        steps,
+        raw_distance,
+        max,
+    };
+    (k_edit_distance_with(s, t, &config), trace)
+}
 
-#[cfg(not(test))]
-use log::debug;
 #[cfg(test)]
-use std::println as debug;
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    /// A step of a small xorshift so the long inputs in tests are random but reproducible
+    pub(crate) fn xorshift(state: &mut u32) -> u32 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *state
+    }
+
+    /// `len` chars picked from `alphabet` with `xorshift`
+    pub(crate) fn random_string(state: &mut u32, len: usize, alphabet: &[char]) -> String {
+        (0..len)
+            .map(|_| alphabet[xorshift(state) as usize % alphabet.len()])
+            .collect()
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("hello", "hello"), 0);
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("kitten", "sitten"), 1);
+        assert_eq!(levenshtein_distance("sitten", "sittin"), 1);
+        assert_eq!(levenshtein_distance("sittin", "sitting"), 1);
+        assert_eq!(levenshtein_distance("book", "back"), 2);
+        assert_eq!(levenshtein_distance("apple", "back"), 5);
+        assert_eq!(levenshtein_distance("hello", ""), 5);
+    }
+
+    #[test]
+    fn test_levenshtein_generic() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("", ""),
+            ("국어", "숙어"),
+        ] {
+            let (s, t) = (chars(s), chars(t));
+            let dist = levenshtein_distance_chars(&s, &t);
+            assert_eq!(levenshtein_generic(&s, &t, 1usize, 1, |_, _| 1), dist);
+            assert_eq!(levenshtein_generic(&s, &t, 1i32, 1, |_, _| 1), dist as i32);
+            assert_eq!(
+                levenshtein_generic(&s, &t, 1f32, 1., |_, _| 1.),
+                dist as f32
+            );
+            assert_eq!(
+                levenshtein_generic(&s, &t, 1f64, 1., |_, _| 1.),
+                dist as f64
+            );
+            // Halving every cost halves the distance
+            assert_eq!(
+                levenshtein_generic(&s, &t, 0.5f32, 0.5, |_, _| 0.5),
+                dist as f32 / 2.
+            );
+        }
+
+        let (s, t) = (chars("abc"), chars("abcde"));
+        assert_eq!(
+            levenshtein_generic(&s, &t, 2usize, 0, |_, _| 1),
+            weighted_levenshtein("abc", "abcde", 2, 0, 1)
+        );
+        // Signed costs, a reward for substituting
+        assert_eq!(
+            levenshtein_generic(&chars("ab"), &chars("cd"), 1, 1, |_, _| -1),
+            -2
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_banded() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("book", "back"),
+            ("국어", "숙어"),
+            ("abcdefgh", "hgfedcba"),
+        ] {
+            let (s, t) = (chars(s), chars(t));
+            let dist = levenshtein_distance_chars(&s, &t);
+            assert_eq!(levenshtein_banded(&s, &t, dist), Some(dist));
+            assert_eq!(levenshtein_banded(&s, &t, dist + 5), Some(dist));
+            if dist > 0 {
+                assert_eq!(levenshtein_banded(&s, &t, dist - 1), None);
+            }
+            assert_eq!(levenshtein_expanding(&s, &t), dist);
+        }
+        assert_eq!(levenshtein_banded(&[], &[], 0), Some(0));
+        assert_eq!(levenshtein_expanding(&[], &[]), 0);
+
+        let long: Vec<char> = "가나다라마바사".chars().cycle().take(5000).collect();
+        let mut similar = long.clone();
+        similar[100] = 'x';
+        similar.remove(4000);
+        assert_eq!(levenshtein_expanding(&long, &similar), 2);
+    }
+
+    #[test]
+    fn test_myers_distance() {
+        assert_eq!(myers_distance("", ""), 0);
+        assert_eq!(myers_distance("", "abc"), 3);
+        assert_eq!(myers_distance("abc", ""), 3);
+        assert_eq!(myers_distance("book", "back"), 2);
+
+        let mut state: u32 = 0x9e37_79b9;
+        let alphabet = ['a', 'b', 'c', 'd', '가', '나'];
+
+        for i in 0..500 {
+            let s = random_string(&mut state, i % 65, &alphabet);
+            let t = random_string(&mut state, (i * 7) % 40, &alphabet);
+            assert_eq!(
+                myers_distance(&s, &t),
+                levenshtein_distance(&s, &t),
+                "{s} {t}"
+            );
+        }
+
+        // Split over several words
+        for len in 70..=200 {
+            let s = random_string(&mut state, len, &alphabet);
+            let t = random_string(&mut state, len * 3 % 211, &alphabet);
+            assert_eq!(
+                myers_distance(&s, &t),
+                levenshtein_distance(&s, &t),
+                "{s} {t}"
+            );
+            let similar: String = s.chars().skip(3).chain(['a', 'b']).collect();
+            assert_eq!(
+                myers_distance(&s, &similar),
+                levenshtein_distance(&s, &similar),
+            );
+        }
+        for len in [128, 129, 192] {
+            let s = random_string(&mut state, len, &alphabet);
+            assert_eq!(myers_distance(&s, &s), 0);
+            assert_eq!(myers_distance(&s, ""), len);
+        }
+    }
+
+    #[test]
+    fn test_levenshtein_distance_two_rows() {
+        let mut state: u32 = 0x2545_f491;
+        let alphabet = ['a', 'b', 'c', 'd', '가', '나'];
+
+        for (s_len, t_len) in [(0, 50), (120, 0), (200, 180), (97, 256), (300, 300)] {
+            let s = random_string(&mut state, s_len, &alphabet)
+                .chars()
+                .collect::<Vec<_>>();
+            let t = random_string(&mut state, t_len, &alphabet)
+                .chars()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                weighted_levenshtein_chars(&s, &t, 1, 1, 1),
+                levenshtein_matrix(&s, &t)[s_len][t_len]
+            );
+        }
+    }
+
+    #[test]
+    fn test_levenshtein_checked() {
+        assert_eq!(levenshtein_checked("kitten", "sitting"), Ok(3));
+        assert_eq!(levenshtein_checked("", ""), Ok(0));
+        assert_eq!(levenshtein_checked("국어", "국화"), Ok(1));
+
+        let huge = "a".repeat(100_000);
+        assert_eq!(
+            levenshtein_checked(&huge, &huge),
+            Err(DistanceError {
+                s_len: 100_000,
+                t_len: 100_000,
+                max_cells: DEFAULT_CELL_LIMIT,
+            })
+        );
+        // Long against empty is no work at all
+        assert_eq!(levenshtein_checked(&huge, ""), Ok(100_000));
+
+        assert_eq!(
+            levenshtein_checked_with_limit("kitten", "sitting", 42),
+            Ok(3)
+        );
+        assert!(levenshtein_checked_with_limit("kitten", "sitting", 41).is_err());
+        assert!(levenshtein_checked_with_limit("a", "a", 0).is_err());
+        assert_eq!(levenshtein_checked_with_limit("", "a", 0), Ok(1));
+        assert_eq!(
+            levenshtein_checked_with_limit("국어", "국어사전", usize::MAX),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_iter() {
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("flaw", "lawn"),
+            ("국어", "국화"),
+            ("", "abc"),
+            ("abc", ""),
+            ("", ""),
+        ] {
+            assert_eq!(
+                levenshtein_iter(s.chars(), t.chars()),
+                levenshtein_distance(s, t)
+            );
+        }
+        assert_eq!(
+            levenshtein_iter(
+                "the quick brown fox".split(' '),
+                "the quick red fox jumps".split(' ')
+            ),
+            2
+        );
+        assert_eq!(levenshtein_iter(b"kitten".iter(), b"sitting".iter()), 3);
+        assert_eq!(levenshtein_iter(0..5, 1..6), 2);
+    }
+
+    #[test]
+    fn test_levenshtein_slice() {
+        let s: Vec<&str> = "the quick brown fox".split(' ').collect();
+        let t: Vec<&str> = "the quick red fox jumps".split(' ').collect();
+        assert_eq!(levenshtein_slice(&s, &t), 2);
+        assert_eq!(levenshtein_slice(&s, &s), 0);
+        assert_eq!(levenshtein_slice::<&str>(&[], &t), 5);
+        assert_eq!(levenshtein_slice(&[1, 2, 3], &[1, 3]), 1);
+        assert_eq!(levenshtein_slice(b"kitten", b"sitting"), 3);
+
+        let s = "kitten".chars().collect::<Vec<_>>();
+        let t = "sitting".chars().collect::<Vec<_>>();
+        assert_eq!(
+            levenshtein_slice(&s, &t),
+            levenshtein_distance_chars(&s, &t)
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_graphemes() {
+        assert_eq!(levenshtein_graphemes("", ""), 0);
+        assert_eq!(levenshtein_graphemes("kitten", "sitting"), 3);
+        // A base and combining mark is the same as the precomposed form
+        assert_eq!(levenshtein_graphemes("e\u{301}", "é"), 0);
+        assert_eq!(levenshtein_distance("e\u{301}", "é"), 2);
+        assert_eq!(levenshtein_graphemes("nai\u{308}ve", "naïve"), 0);
+        assert_eq!(levenshtein_graphemes("e\u{301}", "e"), 1);
+        assert_eq!(levenshtein_distance("e\u{301}", "e"), 1);
+        // A whole emoji with a skin tone is one cluster
+        assert_eq!(levenshtein_graphemes("👍🏽", "👍🏿"), 1);
+        assert_eq!(levenshtein_distance("👍🏽", "👍"), 1);
+        assert_eq!(levenshtein_graphemes("👍🏽a", "a"), 1);
+        // Jamo written out join into one syllable cluster
+        assert_eq!(levenshtein_graphemes("\u{1100}\u{1161}", "가"), 0);
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        let collapse = LevenshteinConfig {
+            collapse_whitespace: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            levenshtein_distance_with("hello   world", "hello world", &collapse),
+            0
+        );
+        assert_eq!(
+            levenshtein_distance_with("hello\t\n world", "hello world", &collapse),
+            0
+        );
+        assert_eq!(
+            levenshtein_distance_with("  hello world \t", "hello world", &collapse),
+            0
+        );
+        assert_eq!(
+            levenshtein_distance_with("국어\u{3000}사전", "국어 사전", &collapse),
+            0
+        );
+        assert_eq!(levenshtein_distance_with(" \t ", "", &collapse), 0);
+        // Runs become one space, they don't go away
+        assert_eq!(
+            levenshtein_distance_with("hello  world", "helloworld", &collapse),
+            1
+        );
+        assert_eq!(levenshtein_distance("hello   world", "hello world"), 2);
+
+        // The space left where punctuation was is collapsed too
+        let both = LevenshteinConfig {
+            strip_punctuation: true,
+            collapse_whitespace: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            levenshtein_distance_with("hello - world!", "hello world", &both),
+            0
+        );
+    }
+
+    #[test]
+    fn test_strip_punctuation() {
+        let strip = LevenshteinConfig {
+            strip_punctuation: true,
+            ..Default::default()
+        };
+        assert_eq!(levenshtein_distance_with("U.S.A.", "USA", &strip), 0);
+        assert_eq!(levenshtein_distance("U.S.A.", "USA"), 3);
+        assert_eq!(
+            levenshtein_distance_with("U.S.A.", "USA", &LevenshteinConfig::default()),
+            3
+        );
+        assert_eq!(levenshtein_distance_with("don't", "dont", &strip), 0);
+        // Curly quotes, dashes, brackets and underscores are all punctuation
+        assert_eq!(
+            levenshtein_distance_with("“well-known” (snake_case)", "wellknown snakecase", &strip),
+            0
+        );
+        assert_eq!(levenshtein_distance_with("국어!?", "국어", &strip), 0);
+        assert_eq!(levenshtein_distance_with("「국어」。", "국어", &strip), 0);
+        // Symbols and spaces stay
+        assert_eq!(levenshtein_distance_with("a+b=$", "ab", &strip), 3);
+        assert_eq!(levenshtein_distance_with("a b", "ab", &strip), 1);
+        // Full width punctuation once it's folded
+        let both = LevenshteinConfig {
+            fold_width: true,
+            strip_punctuation: true,
+            ..Default::default()
+        };
+        assert_eq!(levenshtein_distance_with("ＵＳＡ！", "USA", &both), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_distance_with() {
+        let fold = LevenshteinConfig {
+            fold_width: true,
+            ..Default::default()
+        };
+        assert_eq!(levenshtein_distance_with("ＡＢ", "AB", &fold), 0);
+        assert_eq!(
+            levenshtein_distance_with("ＡＢ", "AB", &LevenshteinConfig::default()),
+            2
+        );
+        assert_eq!(levenshtein_distance_with("１２３", "123", &fold), 0);
+        assert_eq!(levenshtein_distance_with("a\u{3000}b", "a b", &fold), 0);
+        assert_eq!(levenshtein_distance_with("ｱｲｳ", "アイウ", &fold), 0);
+        // The voicing mark joins the kana instead of being its own char
+        assert_eq!(levenshtein_distance_with("ｶﾞｷﾞ", "ガギ", &fold), 0);
+        assert_eq!(levenshtein_distance_with("ﾡﾤ", "ㄱㄴ", &fold), 0);
+        assert_eq!(levenshtein_distance_with("ＡＢ", "ABC", &fold), 1);
+        // Only the width forms are touched
+        assert_eq!(levenshtein_distance_with("①", "1", &fold), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_distance_ascii_fold() {
+        assert_eq!(levenshtein_distance_ascii_fold("café", "cafe"), 0);
+        assert_eq!(levenshtein_distance_ascii_fold("cafe\u{301}", "cafe"), 0);
+        assert_eq!(levenshtein_distance_ascii_fold("Zürich", "Zurich"), 0);
+        assert_eq!(levenshtein_distance_ascii_fold("naïve", "naive"), 0);
+        assert_eq!(levenshtein_distance_ascii_fold("Ångström", "Angstrom"), 0);
+        // Not transliterated
+        assert_eq!(levenshtein_distance_ascii_fold("ø", "o"), 1);
+        assert_eq!(levenshtein_distance_ascii_fold("straße", "strasse"), 2);
+        // Case still matters
+        assert_eq!(levenshtein_distance_ascii_fold("Café", "cafe"), 1);
+        assert_eq!(levenshtein_distance_ascii_fold("국어", "숙어"), 1);
+        assert_eq!(levenshtein_distance("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_distance_ci() {
+        assert_eq!(levenshtein_distance_ci("ABC", "abc"), 0);
+        assert_eq!(levenshtein_distance_ci("", ""), 0);
+        assert_eq!(levenshtein_distance_ci("Kitten", "SITTING"), 3);
+        assert_eq!(levenshtein_distance_ci("ΣΊΣΥΦΟΣ", "σίσυφοσ"), 0);
+        // İ lowercases to i and a combining dot above
+        assert_eq!(levenshtein_distance_ci("İ", "i\u{307}"), 0);
+        assert_eq!(levenshtein_distance_ci("İ", "i"), 1);
+        assert_eq!(levenshtein_distance_ci("İstanbul", "i̇stanbul"), 0);
+        // Not case folding
+        assert_eq!(levenshtein_distance_ci("ß", "SS"), 2);
+        assert_eq!(levenshtein_distance_ci("국어", "국어"), 0);
+    }
 
-use std::char;
+    #[test]
+    fn test_levenshtein_ops_hirschberg() {
+        use EditOp::*;
 
-use unicode_normalization::UnicodeNormalization;
-use unicode_segmentation::UnicodeSegmentation;
+        fn apply(ops: &[EditOp]) -> (String, String) {
+            let mut s = String::new();
+            let mut t = String::new();
+            for op in ops {
+                match *op {
+                    Match(c) => {
+                        s.push(c);
+                        t.push(c);
+                    }
+                    Substitute(a, b) => {
+                        s.push(a);
+                        t.push(b);
+                    }
+                    Insert(c) => t.push(c),
+                    Delete(c) => s.push(c),
+                }
+            }
+            (s, t)
+        }
+        let edits = |ops: &[EditOp]| ops.iter().filter(|op| !matches!(op, Match(_))).count();
 
-pub fn levenshtein_distance(s: &str, t: &str) -> usize {
-    levenshtein_distance_chars(
-        &s.chars().collect::<Vec<_>>(),
-        &t.chars().collect::<Vec<_>>(),
-    )
-}
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("sitting", "kitten"),
+            ("", ""),
+            ("abc", ""),
+            ("", "abc"),
+            ("a", "b"),
+            ("book", "back"),
+            ("apple", "back"),
+            ("신문", "식물"),
+            ("abcdefghij", "azcdxfghyj"),
+        ] {
+            let s_chars: Vec<char> = s.chars().collect();
+            let t_chars: Vec<char> = t.chars().collect();
+            let ops = levenshtein_ops_hirschberg(&s_chars, &t_chars);
+            assert_eq!(edits(&ops), edits(&levenshtein_ops(s, t)));
+            assert_eq!(apply(&ops), (s.to_string(), t.to_string()));
+        }
 
-fn levenshtein_distance_chars(s: &[char], t: &[char]) -> usize {
-    let m = s.len();
-    let n = t.len();
-    let mut d = vec![vec![0; n + 1]; m + 1];
+        // Long enough that it splits many times
+        let mut state: u32 = 0x9e37_79b9;
+        let alphabet = ['a', 'b', 'c', 'd'];
+        for (s_len, t_len) in [(300, 280), (250, 320), (400, 400)] {
+            let s = random_string(&mut state, s_len, &alphabet);
+            let t = random_string(&mut state, t_len, &alphabet);
+            let s_chars: Vec<char> = s.chars().collect();
+            let t_chars: Vec<char> = t.chars().collect();
+            let ops = levenshtein_ops_hirschberg(&s_chars, &t_chars);
+            assert_eq!(edits(&ops), levenshtein_distance(&s, &t));
+            assert_eq!(apply(&ops), (s, t));
+        }
+    }
 
-    for i in 1..=m {
-        d[i][0] = i;
+    #[test]
+    fn test_levenshtein_ratio() {
+        assert_eq!(levenshtein_ratio("hello", "hello"), 1.);
+        assert_eq!(levenshtein_ratio("", ""), 1.);
+        assert_eq!(levenshtein_ratio("hello", ""), 0.);
+        assert_eq!(levenshtein_ratio("book", "back"), 0.5);
+        assert_eq!(levenshtein_ratio("kitten", "sitting"), 1. - 3. / 7.);
+        // Measured in chars, not bytes
+        assert_eq!(levenshtein_ratio("국어", "숙어"), 0.5);
     }
 
-    for j in 1..=n {
-        d[0][j] = j;
+    #[test]
+    fn test_levenshtein_ops() {
+        use EditOp::*;
+
+        assert_eq!(
+            levenshtein_ops("kitten", "sitting"),
+            vec![
+                Substitute('k', 's'),
+                Match('i'),
+                Match('t'),
+                Match('t'),
+                Substitute('e', 'i'),
+                Match('n'),
+                Insert('g'),
+            ]
+        );
+        assert_eq!(levenshtein_ops("", ""), vec![]);
+        assert_eq!(levenshtein_ops("ab", ""), vec![Delete('a'), Delete('b')]);
+        assert_eq!(levenshtein_ops("", "ab"), vec![Insert('a'), Insert('b')]);
+
+        for (s, t) in [
+            ("hello", "hello"),
+            ("book", "back"),
+            ("apple", "back"),
+            ("신문", "식물"),
+            ("abcdef", "azced"),
+        ] {
+            let ops = levenshtein_ops(s, t);
+            let edits = ops.iter().filter(|op| !matches!(op, Match(_))).count();
+            assert_eq!(edits, levenshtein_distance(s, t));
+        }
     }
 
-    for j in 1..=n {
-        for i in 1..=m {
-            let substitution_cost = if s[i - 1] == t[j - 1] { 0 } else { 1 };
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let ops = levenshtein_ops("kitten", "sitting");
+        let json = serde_json::to_string(&ops).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<EditOp>>(&json).unwrap(), ops);
 
-            d[i][j] = (d[i - 1][j] + 1)
-                .min(d[i][j - 1] + 1)
-                .min(d[i - 1][j - 1] + substitution_cost);
+        let config = KEditConfig {
+            consonant_normalization: false,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<KEditConfig>(&json).unwrap(), config);
+        // Anything left out is the default
+        assert_eq!(
+            serde_json::from_str::<KEditConfig>("{}").unwrap(),
+            KEditConfig::default()
+        );
+
+        // The types with Vec and String fields
+        let (_, trace) = k_edit_distance_traced("국어", "숙어사전");
+        let json = serde_json::to_string(&trace).unwrap();
+        assert_eq!(serde_json::from_str::<KTrace>(&json).unwrap(), trace);
+
+        let breakdown = k_edit_distance_breakdown("국어", "숙어사전");
+        let json = serde_json::to_string(&breakdown).unwrap();
+        assert_eq!(
+            serde_json::from_str::<KBreakdown>(&json).unwrap(),
+            breakdown
+        );
+    }
+
+    #[test]
+    fn test_weighted_levenshtein() {
+        for (s, t) in [
+            ("hello", "hello"),
+            ("", ""),
+            ("kitten", "sitting"),
+            ("book", "back"),
+            ("apple", "back"),
+            ("hello", ""),
+        ] {
+            assert_eq!(
+                weighted_levenshtein(s, t, 1, 1, 1),
+                levenshtein_distance(s, t)
+            );
         }
+
+        assert_eq!(weighted_levenshtein("", "abc", 2, 5, 7), 6);
+        assert_eq!(weighted_levenshtein("abc", "", 2, 5, 7), 15);
+        // A substitution is cheaper as a delete and insert here
+        assert_eq!(weighted_levenshtein("a", "b", 1, 1, 5), 2);
+        assert_eq!(weighted_levenshtein("kitten", "sitting", 1, 1, 2), 5);
+        assert_eq!(weighted_levenshtein("kitten", "sitting", 3, 1, 1), 5);
     }
 
-    d[m][n]
-}
+    #[test]
+    fn test_levenshtein_with() {
+        let confusion: BTreeMap<(char, char), usize> = [
+            (('0', 'O'), 0),
+            (('1', 'l'), 0),
+            (('5', 'S'), 0),
+            (('8', 'B'), 0),
+        ]
+        .into_iter()
+        .collect();
+        let sub_cost = |a: char, b: char| {
+            *confusion
+                .get(&(a, b))
+                .or_else(|| confusion.get(&(b, a)))
+                .unwrap_or(&1)
+        };
 
-fn normalize(s: &str) -> Vec<char> {
-    let mut normalized = vec![];
+        assert_eq!(levenshtein_distance("HELL0 W0RLD", "HELLO WORLD"), 2);
+        assert_eq!(levenshtein_with("HELL0 W0RLD", "HELLO WORLD", sub_cost), 0);
+        assert_eq!(levenshtein_distance("1ab5 8us", "labS Bus"), 3);
+        assert_eq!(levenshtein_with("1ab5 8us", "labS Bus", sub_cost), 0);
+        // Things outside the map still cost the usual amount
+        assert_eq!(levenshtein_with("kitten", "sitting", sub_cost), 3);
+        assert_eq!(levenshtein_with("HELL0", "HELLX", sub_cost), 1);
+    }
 
-    for unicode_char in s.graphemes(true) {
-        for part in unicode_char.chars() {
-            for char in part.nfd() {
-                if char == ' ' {
-                    continue;
-                }
-                normalized.push(match char {
-                    'ㄱ' | 'ㅋ' | 'ㄲ' => 'ㄱ',
-                    'ㄷ' | 'ㄸ' | 'ㅌ' => 'ㄷ',
-                    'ㅂ' | 'ㅃ' | 'ㅍ' => 'ㅂ',
-                    'ㅅ' | 'ㅆ' => 'ㅅ',
-                    'ㅈ' | 'ㅉ' | 'ㅊ' => 'ㅈ',
-                    _ => char,
-                });
+    #[test]
+    fn test_incremental_levenshtein() {
+        for (target, typed) in [
+            ("sitting", "kitten"),
+            ("국어", "한국어"),
+            ("", "abc"),
+            ("abc", ""),
+        ] {
+            let mut incremental = IncrementalLevenshtein::new(target);
+            assert_eq!(incremental.distance(), levenshtein_distance("", target));
+
+            let mut query = String::new();
+            for c in typed.chars() {
+                query.push(c);
+                assert_eq!(incremental.push(c), levenshtein_distance(&query, target));
+            }
+            while query.pop().is_some() {
+                assert_eq!(incremental.pop(), levenshtein_distance(&query, target));
             }
+            // Backspacing an empty query does nothing
+            assert_eq!(incremental.pop(), target.chars().count());
         }
     }
 
-    normalized
-}
+    #[test]
+    fn test_classed_levenshtein() {
+        assert!(
+            classed_levenshtein("abc1", "abc2", 0.5, 1.0)
+                < classed_levenshtein("abc1", "abcX", 0.5, 1.0)
+        );
+        assert_eq!(classed_levenshtein("abc1", "abc2", 0.5, 1.0), 0.5);
+        assert_eq!(classed_levenshtein("abc1", "abcX", 0.5, 1.0), 1.0);
+        assert_eq!(classed_levenshtein("국어", "숙어", 0.25, 1.0), 0.25);
+        assert_eq!(classed_levenshtein("a b", "a-b", 0.5, 0.75), 0.75);
+        assert_eq!(classed_levenshtein("a!", "a?", 0.5, 0.75), 0.5);
+        // Unicode digits are numbers too
+        assert_eq!(classed_levenshtein("1", "٣", 0.5, 1.0), 0.5);
+        assert_eq!(classed_levenshtein("abc", "abc", 0.5, 1.0), 0.0);
+        assert_eq!(classed_levenshtein("abc", "", 0.5, 1.0), 3.0);
+        // Substituting is never worth more than deleting and inserting
+        assert_eq!(classed_levenshtein("a", "1", 0.5, 5.0), 2.0);
+    }
 
-/// Returns 1.0 for most different and 0.0 for exactly the same.
-/// Implementation of "Word Similarity Calculation by Using the Edit Distance Metrics with Consonant Normalization" https://web.archive.org/web/20260112025218/https://koreascience.kr/article/JAKO201502152089381.pdf from Kang Seung Shik I'm not sure if it's 100% correct.
-///
-/// # Examples
-///
-/// ```
-/// let distance = k_edit_distance::k_edit_distance("국어", "숙어");
-/// assert_eq!(distance, 0.16666667);
-/// let distance = k_edit_distance::k_edit_distance("신문", "신문");
-/// assert_eq!(distance, 0.0);
-/// let distance = k_edit_distance::k_edit_distance("하늘", "택시");
-/// assert_eq!(distance, 1.0);
-/// ```
-pub fn k_edit_distance(s: &str, t: &str) -> f32 {
-    if s.len() == 0 && t.len() == 0 {
-        return 0.;
+    #[test]
+    fn test_script_weighted_distance() {
+        // A Latin typo is cheaper than Hangul where a Latin letter should be
+        assert!(
+            script_weighted_distance("cat", "cut", 0.5, 1.0)
+                < script_weighted_distance("cat", "c어t", 0.5, 1.0)
+        );
+        assert_eq!(script_weighted_distance("cat", "cut", 0.5, 1.0), 0.5);
+        assert_eq!(script_weighted_distance("cat", "c어t", 0.5, 1.0), 1.0);
+        assert_eq!(script_weighted_distance("국어", "숙어", 0.25, 1.0), 0.25);
+        // Hangul and Han are different scripts, and so are kana and kanji
+        assert_eq!(script_weighted_distance("韓", "한", 0.25, 1.0), 1.0);
+        assert_eq!(script_weighted_distance("か", "加", 0.25, 1.0), 1.0);
+        assert_eq!(script_weighted_distance("か", "き", 0.25, 1.0), 0.25);
+        // Digits and punctuation go with anything
+        assert_eq!(script_weighted_distance("a1", "a한", 0.5, 1.0), 0.5);
+        assert_eq!(script_weighted_distance("a!", "a?", 0.5, 1.0), 0.5);
+        assert_eq!(script_weighted_distance("abc", "abc", 0.5, 1.0), 0.0);
+        assert_eq!(script_weighted_distance("abc", "", 0.5, 1.0), 3.0);
+        // Substituting is never worth more than deleting and inserting
+        assert_eq!(script_weighted_distance("a", "한", 0.5, 5.0), 2.0);
     }
-    debug!("{} to {}", s, t);
 
-    // break each string into syllables
-    let s_syllables: Vec<_> = s.graphemes(true).collect();
-    let t_syllables: Vec<_> = t.graphemes(true).collect();
+    #[test]
+    fn test_levenshtein_struct() {
+        let mut levenshtein = Levenshtein::new();
+        // Long then short then long again to check the buffers are reset properly
+        for (s, t) in [
+            ("hello", "hello"),
+            ("", ""),
+            ("kitten", "sitting"),
+            ("book", "back"),
+            ("a", ""),
+            ("", "abc"),
+            ("apple", "back"),
+            ("신문", "식물"),
+            ("진공청소기", "솥"),
+            ("hello", ""),
+        ] {
+            assert_eq!(levenshtein.distance(s, t), levenshtein_distance(s, t));
+        }
+    }
 
-    let mut edit_distance = 0;
-    for i in 0..(s_syllables.len().max(t_syllables.len())) {
-        let s_part = s_syllables.get(i).unwrap_or(&"");
-        let s_norm = normalize(s_part);
-        let t_part = t_syllables.get(i).unwrap_or(&"");
-        let t_norm = normalize(t_part);
+    #[test]
+    fn test_levenshtein_within() {
+        // Only exact matches
+        assert_eq!(levenshtein_within("hello", "hello", 0), Some(0));
+        assert_eq!(levenshtein_within("hello", "hallo", 0), None);
+        assert_eq!(levenshtein_within("", "", 0), Some(0));
+        // Just inside and just outside the bound
+        assert_eq!(levenshtein_within("book", "back", 2), Some(2));
+        assert_eq!(levenshtein_within("book", "back", 1), None);
+        assert_eq!(levenshtein_within("apple", "back", 5), Some(5));
+        assert_eq!(levenshtein_within("apple", "back", 4), None);
+        // The lengths alone are too far apart
+        assert_eq!(levenshtein_within("hello", "", 4), None);
+        assert_eq!(levenshtein_within("a", "abcd", 2), None);
+        assert_eq!(levenshtein_within("hello", "", 5), Some(5));
+        assert_eq!(levenshtein_within("sittin", "sitting", 1), Some(1));
+        // Generous bounds should agree with the full distance
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("신문", "식물"),
+            ("abcdef", "fedcba"),
+        ] {
+            assert_eq!(
+                levenshtein_within(s, t, 10),
+                Some(levenshtein_distance(s, t))
+            );
+        }
+    }
 
-        let syllable_dist = levenshtein_distance_chars(&s_norm, &t_norm);
+    #[test]
+    fn test_sift4() {
+        assert_eq!(sift4("", "", 5), 0);
+        assert_eq!(sift4("abc", "", 5), 3);
+        assert_eq!(sift4("", "abcd", 5), 4);
+        for word in ["hello", "국어", "a", "진공청소기"] {
+            assert_eq!(sift4(word, word, 5), 0);
+        }
+        assert_eq!(sift4("hello", "hallo", 5), 1);
+        assert_eq!(sift4("kitten", "sitting", 5), 3);
+        // Gets bigger the more different they are
+        assert!(sift4("hello", "hallo", 5) < sift4("hello", "hxllx", 5));
+        assert!(sift4("hello", "hxllx", 5) < sift4("hello", "xxxxx", 5));
+        assert_eq!(sift4("hello", "xyzwv", 5), 5);
+        // Where it disagrees with the real distance
+        assert_eq!(sift4("abc", "bca", 5), 1);
+        assert_eq!(levenshtein_distance("abc", "bca"), 2);
+    }
 
-        edit_distance += syllable_dist;
-        debug!(
-            "{} {}({}) ({}({:?}) {}({:?}))",
-            i, edit_distance, syllable_dist, s_part, s_norm, t_part, t_norm
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance("karolin", "kathrin"), Ok(3));
+        assert_eq!(hamming_distance("1011101", "1001001"), Ok(2));
+        assert_eq!(hamming_distance("", ""), Ok(0));
+        assert_eq!(hamming_distance("국어", "숙어"), Ok(1));
+        assert_eq!(
+            hamming_distance("karolin", "kath"),
+            Err(LengthMismatch { s_len: 7, t_len: 4 })
+        );
+        assert_eq!(
+            hamming_distance("", "a"),
+            Err(LengthMismatch { s_len: 0, t_len: 1 })
         );
     }
 
-    let max = (3 * s_syllables.len()).max(3 * t_syllables.len());
-    let n = edit_distance as f32 / max as f32;
-    debug!("{} / {} = {}", edit_distance, max, n);
+    #[test]
+    fn test_jaro_similarity() {
+        assert_eq!(jaro_similarity("", ""), 1.);
+        assert_eq!(jaro_similarity("abc", ""), 0.);
+        assert_eq!(jaro_similarity("abc", "xyz"), 0.);
+        assert_eq!(jaro_similarity("신문", "신문"), 1.);
+        assert!((jaro_similarity("MARTHA", "MARHTA") - 0.944).abs() < 0.001);
+        assert!((jaro_similarity("DIXON", "DICKSONX") - 0.767).abs() < 0.001);
+        assert!((jaro_similarity("JELLYFISH", "SMELLYFISH") - 0.896).abs() < 0.001);
+    }
 
-    n
-}
+    #[test]
+    fn test_jaro_winkler_similarity() {
+        assert_eq!(jaro_winkler_similarity("", ""), 1.);
+        assert_eq!(jaro_winkler_similarity("abc", "xyz"), 0.);
+        assert_eq!(jaro_winkler_similarity("MARTHA", "MARTHA"), 1.);
+        assert!((jaro_winkler_similarity("MARTHA", "MARHTA") - 0.961).abs() < 0.001);
+        assert!((jaro_winkler_similarity("DIXON", "DICKSONX") - 0.813).abs() < 0.001);
+        assert!((jaro_winkler_similarity("DWAYNE", "DUANE") - 0.84).abs() < 0.001);
+    }
 
-#[cfg(test)]
-mod tests {
-    // Note this useful idiom: importing names from outer (for mod tests) scope.
-    use super::*;
-    use test::Bencher;
+    #[test]
+    fn test_damerau_levenshtein_distance() {
+        assert_eq!(damerau_levenshtein_distance("", ""), 0);
+        assert_eq!(damerau_levenshtein_distance("a", ""), 1);
+        assert_eq!(damerau_levenshtein_distance("", "ab"), 2);
+        assert_eq!(damerau_levenshtein_distance("a", "b"), 1);
+        assert_eq!(damerau_levenshtein_distance("ca", "ac"), 1);
+        assert_eq!(damerau_levenshtein_distance("teh", "the"), 1);
+        assert_eq!(damerau_levenshtein_distance("ca", "abc"), 2);
+        assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein_distance("국어", "어국"), 1);
+
+        assert_eq!(damerau_levenshtein_slice(&[1, 2, 3], &[2, 1, 3]), 1);
+        assert_eq!(damerau_levenshtein_slice(b"ca", b"abc"), 2);
+        assert_eq!(damerau_levenshtein_slice::<u8>(&[], b"ab"), 2);
+    }
 
     #[test]
-    fn test_levenshtein_distance() {
-        assert_eq!(levenshtein_distance("hello", "hello"), 0);
-        assert_eq!(levenshtein_distance("", ""), 0);
-        assert_eq!(levenshtein_distance("kitten", "sitten"), 1);
-        assert_eq!(levenshtein_distance("sitten", "sittin"), 1);
-        assert_eq!(levenshtein_distance("sittin", "sitting"), 1);
-        assert_eq!(levenshtein_distance("book", "back"), 2);
-        assert_eq!(levenshtein_distance("apple", "back"), 5);
-        assert_eq!(levenshtein_distance("hello", ""), 5);
+    fn test_osa_distance() {
+        assert_eq!(osa_distance("", ""), 0);
+        assert_eq!(osa_distance("a", ""), 1);
+        assert_eq!(osa_distance("ca", "ac"), 1);
+        assert_eq!(osa_distance("teh", "the"), 1);
+        assert_eq!(osa_distance("kitten", "sitting"), 3);
+        // This is where it differs from the full Damerau-Levenshtein distance
+        assert_eq!(osa_distance("ca", "abc"), 3);
+        assert_ne!(
+            osa_distance("ca", "abc"),
+            damerau_levenshtein_distance("ca", "abc")
+        );
+    }
+
+    #[test]
+    fn test_to_jamo_string() {
+        assert_eq!(
+            to_jamo_string("국어"),
+            "\u{1100}\u{116E}\u{11A8}\u{110B}\u{1165}"
+        );
+        assert_eq!(to_jamo_string("국어").chars().count(), 5);
+        assert_eq!(to_jamo_string(""), "");
+        assert_eq!(to_jamo_string(" "), "");
+        for s in ["국어", "닭", "ㅋ", "hello 세상"] {
+            assert_eq!(
+                to_jamo_string(s),
+                normalize(s).into_iter().collect::<String>()
+            );
+        }
+
+        // When the differences are inside syllables lined up with each other the plain
+        // distance between the jamo is the same as the raw syllable distance
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("신문", "신문"),
+            ("국어", "쿡어"),
+            ("학교", "학고"),
+        ] {
+            assert_eq!(
+                levenshtein_distance(to_jamo_string(s), to_jamo_string(t)),
+                k_edit_distance_raw(s, t).0
+            );
+        }
+        // Otherwise it can be less, since jamo can line up across syllables
+        assert!(
+            levenshtein_distance(to_jamo_string("하늘"), to_jamo_string("택시"))
+                < k_edit_distance_raw("하늘", "택시").0
+        );
+
+        let config = KEditConfig {
+            consonant_normalization: false,
+            ..Default::default()
+        };
+        assert_ne!(
+            to_jamo_string_with("쿡", &config),
+            to_jamo_string_with("국", &config)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("가"), vec!['\u{1100}', '\u{1161}']);
+        assert_eq!(normalize("각"), vec!['\u{1100}', '\u{1161}', '\u{11A8}']);
+        assert_eq!(normalize("까"), normalize("가"));
+        assert_eq!(normalize("팥"), normalize("받"));
+        assert_eq!(normalize("찻"), normalize("잣"));
+        assert_eq!(normalize("샀"), normalize("삿"));
+        assert_ne!(normalize("가"), normalize("나"));
+        assert_eq!(normalize("ㅋ"), vec!['ᄀ']);
+        assert_eq!(normalize("ㅋ"), normalize("ᄏ"));
+        assert_eq!(normalize("ㅏ"), vec!['ᅡ']);
+        assert_eq!(normalize("가ㄴ"), vec!['ᄀ', 'ᅡ', 'ᄂ']);
+        assert_eq!(normalize("\u{1100}\u{1161}"), normalize("가"));
+        assert_eq!(normalize(" "), vec![]);
+        assert_eq!(normalize("abc"), vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_k_edit_distance_jamo() {
+        assert_eq!(k_edit_distance("ㄱ", "ㅋ"), 0.);
+        assert_eq!(k_edit_distance("ㄱ", "ᄀ"), 0.);
+        assert_eq!(k_edit_distance("ㄱ", "ㄴ"), 1. / 3.);
+        // Missing the vowel
+        assert_eq!(k_edit_distance("ㄱ", "가"), 1. / 3.);
+        // Already decomposed jamo are one syllable
+        assert_eq!(k_edit_distance_raw("\u{1100}\u{1161}", "가"), (0, 3));
+        assert_eq!(
+            k_edit_distance_raw("\u{1100}\u{1161}\u{11AB}", "간"),
+            (0, 3)
+        );
+        // A bare jamo after a syllable is a syllable of its own, and it's a leading ㄴ
+        assert_eq!(k_edit_distance_raw("가ㄴ", "가나"), (1, 6));
+        assert_eq!(k_edit_distance_raw("가ㄴ", "간"), (2, 6));
+        assert_eq!(k_edit_distance_raw("가ㄴ", "가ㄴ"), (0, 6));
     }
 
     #[test]
@@ -143,31 +3265,696 @@ mod tests {
         // These are super different
         assert_eq!(k_edit_distance("검은색", "분홍색"), 0.6666667);
         assert_eq!(k_edit_distance("신호등", "택시"), 0.8888889);
-        assert_eq!(k_edit_distance("진공청소기", "솥"), 0.8666667);
+        // 솥 lines up with 소 rather than 진
+        assert_eq!(k_edit_distance("진공청소기", "솥"), 0.8);
         assert_eq!(k_edit_distance("하늘", "택시"), 1.0);
 
         assert_eq!(k_edit_distance("", ""), 0.);
     }
 
-    #[bench]
-    fn bench_add_two(b: &mut Bencher) {
-        const WORDS: &[&str] = &[
+    #[test]
+    fn test_k_edit_distance_best() {
+        let strict = KEditConfig {
+            consonant_normalization: false,
+            ..Default::default()
+        };
+        let folded = KEditConfig::default();
+
+        assert!(k_edit_distance_with("박수", "팍쑤", &strict) > k_edit_distance("박수", "팍쑤"));
+        assert_eq!(
+            k_edit_distance_best("박수", "팍쑤", &[strict.clone(), folded.clone()]),
+            0.
+        );
+        assert_eq!(
+            k_edit_distance_best("박수", "팍쑤", core::slice::from_ref(&strict)),
+            k_edit_distance_with("박수", "팍쑤", &strict)
+        );
+        // The order doesn't matter
+        assert_eq!(
+            k_edit_distance_best("국어", "숙어", &[folded.clone(), strict.clone()]),
+            k_edit_distance_best("국어", "숙어", &[strict, folded])
+        );
+        assert_eq!(
+            k_edit_distance_best("국어", "숙어", &[]),
+            k_edit_distance("국어", "숙어")
+        );
+    }
+
+    #[test]
+    fn test_as_ref_inputs() {
+        let owned = String::from("국어");
+        assert_eq!(k_edit_distance(String::from("국어"), "숙어"), 1. / 6.);
+        assert_eq!(k_edit_distance(&owned, &owned), 0.);
+        assert_eq!(k_edit_distance_raw(Cow::Borrowed("국어"), &owned), (0, 6));
+        assert_eq!(levenshtein_distance(&owned, String::from("숙어")), 1);
+        assert_eq!(damerau_levenshtein_distance(owned.clone(), "어국"), 1);
+        assert_eq!(
+            k_edit_distance_breakdown(&owned, String::from("숙어")),
+            k_edit_distance_breakdown("국어", "숙어")
+        );
+        assert_eq!(levenshtein_ops(&owned, &owned).len(), 2);
+        assert_eq!(
+            weighted_levenshtein(String::from("abc"), "abcde", 1, 0, 1),
+            2
+        );
+        assert_eq!(hamming_distance(&owned, String::from("숙어")), Ok(1));
+        assert_eq!(lcs_length(&owned, "국화"), 1);
+    }
+
+    #[test]
+    fn test_k_edit_distance_f64() {
+        assert_eq!(k_edit_distance_f64("국어", "숙어"), 1.0 / 6.0);
+        assert_eq!(k_edit_distance_f64("", ""), 0.0);
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("진공청소기", "솥"),
+            ("게임", "개임"),
+            ("abc", ""),
+        ] {
+            assert_eq!(k_edit_distance_f64(s, t) as f32, k_edit_distance(s, t));
+        }
+    }
+
+    #[test]
+    fn test_k_edit_distance_empty() {
+        assert_eq!(k_edit_distance("", ""), 0.);
+        assert_eq!(k_edit_distance(" ", ""), 0.);
+        assert_eq!(k_edit_distance("  ", " "), 0.);
+        assert_eq!(k_edit_distance("\t\u{3000}", ""), 0.);
+        assert_eq!(k_edit_distance(" 국어 ", "국어"), 0.);
+        assert_eq!(
+            k_edit_distance("국 어", "숙어"),
+            k_edit_distance("국어", "숙어")
+        );
+        // Five jamo out of six
+        assert_eq!(k_edit_distance("", "국어"), 5. / 6.);
+
+        // A combining mark on its own is a grapheme of one jamo
+        assert_eq!(k_edit_distance_raw("\u{301}", ""), (1, 3));
+        assert_eq!(k_edit_distance("\u{301}", "\u{301}"), 0.);
+        // Both marks are one grapheme
+        assert_eq!(k_edit_distance_raw("\u{301}\u{301}", ""), (2, 3));
+
+        // Not stripping makes whitespace count like anything else
+        let keep = KEditConfig {
+            strip_whitespace: false,
+            ..Default::default()
+        };
+        assert_eq!(k_edit_distance_with(" ", "", &keep), 1. / 3.);
+        assert_eq!(k_edit_distance_with("  ", " ", &keep), 1. / 6.);
+    }
+
+    #[test]
+    fn test_k_edit_distance_raw() {
+        assert_eq!(k_edit_distance_raw("국어", "숙어"), (1, 6));
+        assert_eq!(k_edit_distance_raw("나무가지", "나뭇가지"), (1, 12));
+        assert_eq!(k_edit_distance_raw("하늘", "택시"), (6, 6));
+        assert_eq!(k_edit_distance_raw("", ""), (0, 0));
+        assert_eq!(k_edit_distance_raw(" ", ""), (0, 0));
+        // The space isn't a syllable
+        assert_eq!(k_edit_distance_raw("국 어", "숙어"), (1, 6));
+
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("나무가지", "나뭇가지"),
+            ("신문", "식물"),
+            ("검은색", "분홍색"),
+            ("신호등", "택시"),
+            ("진공청소기", "솥"),
+            ("하늘", "택시"),
+        ] {
+            let (edit_distance, max) = k_edit_distance_raw(s, t);
+            assert_eq!(edit_distance as f32 / max as f32, k_edit_distance(s, t));
+        }
+    }
+
+    #[test]
+    fn test_k_edit_distance_traced() {
+        let (score, trace) = k_edit_distance_traced("나뭇 가지", "가지");
+        assert_eq!(score, k_edit_distance("나뭇 가지", "가지"));
+        assert_eq!(trace.s_syllables, vec!["나", "뭇", "가", "지"]);
+        assert_eq!(trace.t_syllables, vec!["가", "지"]);
+        assert_eq!(trace.s_jamo.len(), 4);
+        assert_eq!(trace.t_jamo[1], vec!['ᄌ', 'ᅵ']);
+        assert_eq!((trace.raw_distance, trace.max), (5, 12));
+        // Every syllable of the longer string is compared once
+        assert_eq!(trace.steps.len(), 4);
+        let steps: Vec<_> = trace
+            .steps
+            .iter()
+            .map(|step| (step.s_index, step.t_index, step.running_distance))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (Some(0), None, 2),
+                (Some(1), None, 5),
+                (Some(2), Some(0), 5),
+                (Some(3), Some(1), 5),
+            ]
+        );
+
+        let (score, trace) = k_edit_distance_traced("", "");
+        assert_eq!(score, 0.);
+        assert_eq!(trace.steps, vec![]);
+    }
+
+    #[test]
+    fn test_k_edit_distance_breakdown() {
+        let breakdown = k_edit_distance_breakdown("나뭇가지", "가지");
+        assert_eq!(breakdown.raw_distance, 5);
+        assert_eq!(breakdown.max, 12);
+        assert_eq!(breakdown.score, k_edit_distance("나뭇가지", "가지"));
+        let pairs: Vec<_> = breakdown
+            .syllables
+            .iter()
+            .map(|pair| (pair.s.as_deref(), pair.t.as_deref(), pair.distance))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Some("나"), None, 2),
+                (Some("뭇"), None, 3),
+                (Some("가"), Some("가"), 0),
+                (Some("지"), Some("지"), 0),
+            ]
+        );
+
+        let empty = k_edit_distance_breakdown("", "");
+        assert_eq!(empty.score, 0.);
+        assert_eq!(empty.syllables, vec![]);
+
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("나무가지", "나뭇가지"),
+            ("신문", "식물"),
+            ("검은색", "분홍색"),
+            ("신호등", "택시"),
+            ("진공청소기", "솥"),
+            ("하늘", "택시"),
+            ("한국어", "국어"),
+        ] {
+            let breakdown = k_edit_distance_breakdown(s, t);
+            let sum: usize = breakdown.syllables.iter().map(|pair| pair.distance).sum();
+            assert_eq!(sum, breakdown.raw_distance);
+            assert_eq!(
+                (breakdown.raw_distance, breakdown.max),
+                k_edit_distance_raw(s, t)
+            );
+            assert_eq!(breakdown.score, k_edit_distance(s, t));
+        }
+    }
+
+    #[test]
+    fn test_syllable_distance() {
+        // The score has to come out exactly the same as the full matrix the breakdown uses
+        let words = [
+            "",
+            " ",
+            "국어",
+            "숙어",
+            "국 어",
+            "나뭇가지",
+            "진공청소기",
+            "모시금자라남생이잎벌레",
+            "닭",
+            "좋다",
+            "ㄱ",
+            "가ㄴ",
+            "abc",
+            "e\u{301}\u{302}\u{303}\u{304}\u{305}\u{306}\u{307}\u{308}",
+        ];
+        let configs = [
+            KEditConfig::default(),
+            KEditBuilder::new()
+                .aspiration(true)
+                .split_double_finals(true)
+                .strip_whitespace(false)
+                .build(),
+        ];
+        for s in words {
+            for t in words {
+                for config in &configs {
+                    let (_, s_norm) = syllables(s, config);
+                    let (_, t_norm) = syllables(t, config);
+                    let d = syllable_matrix(&s_norm, &t_norm);
+                    assert_eq!(
+                        k_edit_distance_parts(s, t, config),
+                        (d[s_norm.len()][t_norm.len()], s_norm.len(), t_norm.len()),
+                        "{s} {t}"
+                    );
+                }
+            }
+        }
+
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        for (s, t) in [
+            ("", ""),
+            ("abc", ""),
+            ("", "abcdefghij"),
+            ("kitten", "sitting"),
+        ] {
+            assert_eq!(
+                jamo_distance(&chars(s), &chars(t)),
+                levenshtein_distance(s, t)
+            );
+        }
+    }
+
+    #[test]
+    fn test_k_edit_distance_symmetric() {
+        let words = [
+            "",
+            " ",
             "국어",
             "숙어",
+            "국 어",
             "나무가지",
+            "나뭇가지",
             "신문",
+            "식물",
             "검은색",
             "분홍색",
             "진공청소기",
-            "택시",
-            "모시금자라남생이잎벌레",
+            "솥",
+            "닭",
+            "좋다",
+            "조타",
+            "학교에서",
+            "ㄱ",
+            "가ㄴ",
+            "abc",
+            "국어abc",
+            "e\u{301}",
         ];
-        b.iter(|| {
-            for outer in WORDS {
-                for inner in WORDS {
-                    k_edit_distance(inner, outer);
+        let configs = [
+            KEditConfig::default(),
+            KEditBuilder::new()
+                .consonant_normalization(false)
+                .vowel_normalization(true)
+                .strip_whitespace(false)
+                .build(),
+            KEditBuilder::new()
+                .aspiration(true)
+                .split_double_finals(true)
+                .syllable_weight(4)
+                .length_penalty(0.5)
+                .build(),
+        ];
+
+        for s in words {
+            for t in words {
+                assert_eq!(k_edit_distance(s, t), k_edit_distance(t, s), "{s} {t}");
+                assert_eq!(
+                    k_edit_distance_raw(s, t),
+                    k_edit_distance_raw(t, s),
+                    "{s} {t}"
+                );
+                for config in &configs {
+                    assert_eq!(
+                        k_edit_distance_with(s, t, config),
+                        k_edit_distance_with(t, s, config),
+                        "{s} {t} {config:?}"
+                    );
                 }
             }
-        });
+        }
+    }
+
+    #[test]
+    fn test_k_edit_builder() {
+        assert_eq!(KEditBuilder::new().build(), KEditConfig::default());
+        assert_eq!(
+            KEditBuilder::new().distance("국어", "숙어"),
+            k_edit_distance("국어", "숙어")
+        );
+
+        let builder = KEditBuilder::new()
+            .consonant_normalization(false)
+            .vowel_normalization(true)
+            .strip_whitespace(false)
+            .split_double_finals(true)
+            .syllable_weight(4);
+        let config = builder.clone().build();
+        assert!(!config.consonant_normalization);
+        assert!(config.vowel_normalization);
+        assert!(!config.strip_whitespace);
+        assert!(config.split_double_finals);
+        assert_eq!(config.syllable_weight, 4);
+
+        // ㅐ and ㅔ are the same but ㄱ and ㅋ aren't any more
+        assert_eq!(builder.distance("게", "개"), 0.);
+        assert_eq!(builder.distance("게", "케"), 1. / 4.);
+        // The space counts, as a syllable of its own
+        assert_eq!(builder.distance("국 어", "국어"), 1. / 12.);
+        // 닭 is four jamo out of four
+        assert_eq!(builder.distance("닭", "달"), 1. / 4.);
+        assert_eq!(builder.distance("닭", "닭"), 0.);
+    }
+
+    #[test]
+    fn test_position_decay() {
+        let decayed = KEditConfig {
+            position_decay: 1.,
+            ..Default::default()
+        };
+        // The same one jamo difference at the first and the last syllable
+        let first = k_edit_distance_with("국어", "숙어", &decayed);
+        let last = k_edit_distance_with("어국", "어숙", &decayed);
+        assert!(first > last);
+        assert_eq!(first, (1. / (3. + 3. / 2.)) as f32);
+        assert_eq!(last, (1. / 2. / (3. + 3. / 2.)) as f32);
+        // Uniform without it
+        assert_eq!(
+            k_edit_distance("국어", "숙어"),
+            k_edit_distance("어국", "어숙")
+        );
+
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("하늘", "택시택시"),
+            ("나뭇가지", "가지"),
+            ("신문", "신문"),
+            ("", "가"),
+            ("", ""),
+        ] {
+            let distance = k_edit_distance_with(s, t, &decayed);
+            assert!((0. ..=1.).contains(&distance));
+            assert_eq!(distance, k_edit_distance_with(t, s, &decayed));
+        }
+        assert_eq!(k_edit_distance_with("신문", "신문", &decayed), 0.);
+        assert_eq!(k_edit_distance_with("하늘", "택시", &decayed), 1.);
+
+        // A decay of 0 is the same as the default
+        let uniform = KEditConfig {
+            position_decay: 0.,
+            ..Default::default()
+        };
+        assert_eq!(
+            k_edit_distance_with("나뭇가지", "가지", &uniform),
+            k_edit_distance("나뭇가지", "가지")
+        );
+        assert_eq!(
+            KEditBuilder::new()
+                .position_decay(1.)
+                .distance("국어", "숙어"),
+            first
+        );
+    }
+
+    #[test]
+    fn test_length_penalty() {
+        let penalized = KEditConfig {
+            length_penalty: 0.5,
+            ..Default::default()
+        };
+        // 5 of 12 plus half of 2 of 4 syllables
+        assert_eq!(k_edit_distance("나뭇가지", "가지"), 5. / 12.);
+        assert_eq!(
+            k_edit_distance_with("나뭇가지", "가지", &penalized),
+            (5. / 12. + 0.5 * 2. / 4.) as f32
+        );
+        assert!(
+            k_edit_distance_with("가", "가나다라", &penalized) > k_edit_distance("가", "가나다라")
+        );
+        // Never past 1.0
+        let heavy = KEditConfig {
+            length_penalty: 10.,
+            ..Default::default()
+        };
+        assert_eq!(k_edit_distance_with("하늘", "택시택시", &heavy), 1.);
+        // Equal lengths are unchanged
+        for (s, t) in [
+            ("국어", "숙어"),
+            ("하늘", "택시"),
+            ("신문", "신문"),
+            ("", ""),
+        ] {
+            assert_eq!(
+                k_edit_distance_with(s, t, &penalized),
+                k_edit_distance(s, t)
+            );
+            assert_eq!(
+                levenshtein_ratio_with_penalty(s, t, 0.5),
+                levenshtein_ratio(s, t)
+            );
+        }
+
+        assert_eq!(levenshtein_ratio("a", "abcdef"), 1. - 5. / 6.);
+        let ratio = levenshtein_ratio_with_penalty("a", "abcdef", 0.1);
+        assert!(ratio < 1. - 5. / 6. && ratio > 0.);
+        assert_eq!(levenshtein_ratio_with_penalty("a", "abcdef", 1.), 0.);
+        assert_eq!(levenshtein_ratio_with_penalty("", "", 1.), 1.);
+    }
+
+    #[test]
+    fn test_k_edit_distance_consonant_groups() {
+        let nasals = KEditConfig {
+            consonant_groups: ConsonantGroups::new(&[&['ㄴ', 'ㅁ']]).unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(k_edit_distance_with("눈", "문", &nasals), 0.);
+        assert_eq!(k_edit_distance_with("산", "삼", &nasals), 0.);
+        assert_eq!(k_edit_distance("눈", "문"), 1. / 3.);
+        // Only the custom groups are folded
+        assert_eq!(k_edit_distance_with("국", "쿡", &nasals), 1. / 3.);
+        assert_eq!(k_edit_distance("국", "쿡"), 0.);
+    }
+
+    #[test]
+    fn test_k_edit_distance_with() {
+        let off = KEditConfig {
+            consonant_normalization: false,
+            ..Default::default()
+        };
+
+        assert_eq!(k_edit_distance("국어", "쿡어"), 0.);
+        assert_eq!(k_edit_distance_with("국어", "쿡어", &off), 0.16666667);
+        assert_eq!(k_edit_distance("박수", "팍쑤"), 0.);
+        assert_eq!(k_edit_distance_with("박수", "팍쑤", &off), 2. / 6.);
+        assert_eq!(
+            k_edit_distance_with("국어", "숙어", &KEditConfig::default()),
+            k_edit_distance("국어", "숙어")
+        );
+        // Things that were never folded are the same either way
+        assert_eq!(k_edit_distance_with("신문", "식물", &off), 0.33333334);
+    }
+
+    #[test]
+    fn test_vowel_normalization() {
+        let vowels = KEditConfig {
+            vowel_normalization: true,
+            ..Default::default()
+        };
+
+        assert_eq!(k_edit_distance_with("베개", "배개", &vowels), 0.);
+        assert_ne!(k_edit_distance("베개", "배개"), 0.);
+        assert_eq!(k_edit_distance_with("왜", "외", &vowels), 0.);
+        assert_eq!(k_edit_distance_with("웨", "외", &vowels), 0.);
+        assert_ne!(k_edit_distance_with("아", "애", &vowels), 0.);
+        assert_eq!(normalize_with("ㅔ", &vowels), vec!['ᅢ']);
+        // Works alongside the consonant folding
+        assert_eq!(k_edit_distance_with("뻬개", "배개", &vowels), 0.);
+        let vowels_only = KEditConfig {
+            consonant_normalization: false,
+            vowel_normalization: true,
+            ..Default::default()
+        };
+        assert_ne!(k_edit_distance_with("뻬개", "배개", &vowels_only), 0.);
+        assert_eq!(k_edit_distance_with("베개", "배개", &vowels_only), 0.);
+    }
+
+    #[test]
+    fn test_extended_vowel_normalization() {
+        let extended = KEditConfig {
+            extended_vowel_normalization: true,
+            ..Default::default()
+        };
+        let basic = KEditConfig {
+            vowel_normalization: true,
+            ..Default::default()
+        };
+
+        assert_eq!(k_edit_distance_with("얘기", "예기", &extended), 0.);
+        assert_ne!(k_edit_distance_with("얘기", "예기", &basic), 0.);
+        assert_ne!(k_edit_distance("얘기", "예기"), 0.);
+        assert_eq!(k_edit_distance_with("봐", "붜", &extended), 0.);
+        assert_ne!(k_edit_distance_with("봐", "붜", &basic), 0.);
+        // The basic groups are in there too
+        assert_eq!(k_edit_distance_with("베개", "배개", &extended), 0.);
+        assert_eq!(k_edit_distance_with("왜", "웨", &extended), 0.);
+        // Still not the same as the plain vowels
+        assert_ne!(k_edit_distance_with("얘기", "애기", &extended), 0.);
+        assert_eq!(normalize_with("ㅖ", &extended), vec!['ᅤ']);
+    }
+
+    #[test]
+    fn test_normalization_form() {
+        let config = |normalization_form| KEditConfig {
+            normalization_form,
+            ..Default::default()
+        };
+        let nfc = config(NormalizationForm::Nfc);
+        let nfkc = config(NormalizationForm::Nfkc);
+        let nfkd = config(NormalizationForm::Nfkd);
+
+        // Full width katakana is only the same under compatibility forms
+        assert_ne!(normalize("ｱ"), normalize("ア"));
+        assert_eq!(normalize_with("ｱ", &nfkd), normalize_with("ア", &nfkd));
+        assert_eq!(normalize_with("ｱ", &nfkc), normalize_with("ア", &nfkc));
+        assert_eq!(k_edit_distance_with("ＡＢ", "AB", &nfkd), 0.);
+        assert_ne!(k_edit_distance("ＡＢ", "AB"), 0.);
+        assert_eq!(normalize_with("①", &nfkd), vec!['1']);
+
+        // Composed forms keep syllables whole
+        assert_eq!(normalize_with("국", &nfc), vec!['국']);
+        assert_eq!(normalize_with("\u{1100}\u{116E}\u{11A8}", &nfc), vec!['국']);
+        assert_eq!(normalize_with("국", &nfkd), normalize("국"));
+        assert_eq!(
+            k_edit_distance_with("국어", "숙어", &KEditConfig::default()),
+            k_edit_distance("국어", "숙어")
+        );
+    }
+
+    #[test]
+    fn test_aspiration() {
+        let aspiration = KEditConfig {
+            consonant_normalization: false,
+            aspiration: true,
+            ..Default::default()
+        };
+        let plain = KEditConfig {
+            consonant_normalization: false,
+            ..Default::default()
+        };
+
+        for (written, spoken) in [
+            ("좋다", "조타"),
+            ("놓고", "노코"),
+            ("않다", "안타"),
+            ("싫다", "실타"),
+            ("축하", "추카"),
+            ("입학", "이팍"),
+            ("맞히다", "마치다"),
+        ] {
+            assert_eq!(k_edit_distance_with(written, spoken, &aspiration), 0.);
+            assert_ne!(k_edit_distance_with(written, spoken, &plain), 0.);
+            assert_eq!(
+                normalize_with(written, &aspiration),
+                normalize_with(spoken, &aspiration)
+            );
+        }
+
+        // Needs the syllables to be next to each other
+        assert_ne!(k_edit_distance_with("좋 다", "조타", &aspiration), 0.);
+        assert_eq!(k_edit_distance_with("국어", "숙어", &aspiration), 1. / 6.);
+    }
+
+    #[test]
+    fn test_split_double_finals() {
+        let split = KEditConfig {
+            split_double_finals: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            normalize_with("닭", &split),
+            normalize("\u{1103}\u{1161}\u{11AF}\u{11A8}")
+        );
+        assert_eq!(normalize_with("닭", &split).len(), 4);
+        assert_eq!(normalize("닭").len(), 3);
+        assert_eq!(normalize_with("ㄳ", &split), vec!['ᆨ', 'ᆺ']);
+        // The parts get folded like any other consonant, ㄱ and ㅋ are the same
+        assert_eq!(normalize_with("닭", &split)[3], normalize("닼")[2]);
+
+        assert_eq!(k_edit_distance_with("닭", "닭", &split), 0.);
+        // Dropping one of the parts is one edit either way...
+        assert_eq!(k_edit_distance("닭", "달"), 1. / 3.);
+        assert_eq!(k_edit_distance_with("닭", "달", &split), 1. / 3.);
+        assert_eq!(k_edit_distance_with("읽다", "익다", &split), 1. / 6.);
+        // ...but replacing both is two instead of one
+        assert_eq!(k_edit_distance("닭", "담"), 1. / 3.);
+        assert_eq!(k_edit_distance_with("닭", "담", &split), 2. / 3.);
+    }
+
+    #[test]
+    fn test_fold_width() {
+        let fold = KEditConfig {
+            fold_width: true,
+            ..Default::default()
+        };
+
+        assert_eq!(k_edit_distance_with("ＡＢ", "AB", &fold), 0.);
+        assert_ne!(k_edit_distance("ＡＢ", "AB"), 0.);
+        assert_eq!(k_edit_distance_with("ｶﾞ", "ガ", &fold), 0.);
+        // Half width jamo are the compatibility jamo a keyboard types
+        assert_eq!(normalize_with("ﾡ", &fold), normalize("ㄱ"));
+        // Still works when syllables are kept whole
+        let nfc = KEditConfig {
+            normalization_form: NormalizationForm::Nfc,
+            ..fold.clone()
+        };
+        assert_eq!(normalize_with("ｶﾞ", &nfc), vec!['ガ']);
+        assert_eq!(k_edit_distance_with("국어１", "국어1", &nfc), 0.);
+    }
+
+    #[test]
+    fn test_syllable_weight() {
+        let weight = |syllable_weight| KEditConfig {
+            syllable_weight,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            k_edit_distance_with("국어", "숙어", &weight(3)),
+            k_edit_distance("국어", "숙어")
+        );
+        assert_eq!(k_edit_distance_with("국어", "숙어", &weight(6)), 1. / 12.);
+        assert_eq!(k_edit_distance_with("하늘", "택시", &weight(6)), 0.5);
+        assert_eq!(k_edit_distance_with("하늘", "택시", &weight(1)), 3.);
+        assert_eq!(k_edit_distance_with("하늘", "택시", &weight(0)), 0.);
+
+        // Four jamo syllables need a weight of 4 to stay within 1.0
+        let split = KEditConfig {
+            split_double_finals: true,
+            ..Default::default()
+        };
+        assert_eq!(k_edit_distance_with("닭", "", &split), 4. / 3.);
+        let split = KEditConfig {
+            syllable_weight: 4,
+            ..split
+        };
+        assert_eq!(k_edit_distance_with("닭", "", &split), 1.);
+    }
+
+    #[test]
+    fn test_strip_whitespace() {
+        let keep = KEditConfig {
+            strip_whitespace: false,
+            ..Default::default()
+        };
+
+        assert_eq!(normalize("국 어"), normalize("국어"));
+        // Full width space and tab
+        assert_eq!(normalize("국\u{3000}어"), normalize("국어"));
+        assert_eq!(normalize("국\t어\n"), normalize("국어"));
+        assert_eq!(normalize("a b"), vec!['a', 'b']);
+
+        assert_eq!(normalize_with("a b", &keep), vec!['a', ' ', 'b']);
+        assert_eq!(normalize_with("a\tb", &keep), vec!['a', '\t', 'b']);
+        assert_ne!(normalize_with("국\u{3000}어", &keep), normalize("국어"));
+        assert_eq!(k_edit_distance_with(" ", "", &keep), 1. / 3.);
+    }
+
+    #[test]
+    fn test_k_edit_distance_length_mismatch() {
+        // Dropping leading syllables only costs their jamo
+        assert_eq!(k_edit_distance("나뭇가지", "가지"), 5. / 12.);
+        assert_eq!(k_edit_distance("가지", "나뭇가지"), 5. / 12.);
+        assert_eq!(k_edit_distance("한국어", "국어"), 3. / 9.);
+        assert_eq!(k_edit_distance("국어사전", "국어"), 5. / 12.);
+        assert_eq!(k_edit_distance("국어", ""), 5. / 6.);
+        // Lining them up syllable by syllable used to give 8 / 12
+        assert!(k_edit_distance("나뭇가지", "가지") < 8. / 12.);
     }
 }
@@ -0,0 +1,26 @@
+//! Distances on text that's already split into `char`s, for when you have your own
+//! tokenization and don't want to turn it back into a `&str` only for it to be collected into a
+//! `Vec<char>` again. The functions at the crate root that take `&str` are the same thing with
+//! the collecting done for you.
+
+pub use crate::{
+    levenshtein_generic, levenshtein_iter, levenshtein_slice, normalize, normalize_with,
+};
+
+/// `levenshtein_distance` on `char`s you already have.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::low_level::{levenshtein_distance_chars, normalize};
+///
+/// let s: Vec<char> = vec!['k', 'i', 't', 't', 'e', 'n'];
+/// let t: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(levenshtein_distance_chars(&s, &t), 3);
+///
+/// // The jamo distance k_edit_distance uses for a pair of syllables
+/// assert_eq!(levenshtein_distance_chars(&normalize("국"), &normalize("숙")), 1);
+/// ```
+pub fn levenshtein_distance_chars(s: &[char], t: &[char]) -> usize {
+    levenshtein_slice(s, t)
+}
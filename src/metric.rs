@@ -0,0 +1,280 @@
+//! A common trait over the distance functions so code can take any of them, or swap between
+//! them at runtime with `Box<dyn DistanceMetric>`.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use crate::{damerau_levenshtein_distance, k_edit_distance, levenshtein_distance, osa_distance};
+
+/// Something that can say how far apart two strings are, smaller is closer.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{DamerauMetric, DistanceMetric, KEditMetric, LevenshteinMetric};
+///
+/// let metrics: Vec<Box<dyn DistanceMetric>> = vec![
+///     Box::new(LevenshteinMetric),
+///     Box::new(DamerauMetric),
+///     Box::new(KEditMetric),
+/// ];
+/// let distances: Vec<f64> = metrics.iter().map(|m| m.distance("ca", "ac")).collect();
+/// assert_eq!(distances[..2], [2.0, 1.0]);
+/// assert!(distances[2] < 1.0);
+/// ```
+pub trait DistanceMetric {
+    fn distance(&self, s: &str, t: &str) -> f64;
+
+    /// Whether `distance(s, t)` is always the same as `distance(t, s)`, so `CachedDistance` can
+    /// store both in one entry. False unless a metric says otherwise, all of the ones in this
+    /// crate are.
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+}
+
+/// `levenshtein_distance` as a `DistanceMetric`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LevenshteinMetric;
+
+impl DistanceMetric for LevenshteinMetric {
+    fn distance(&self, s: &str, t: &str) -> f64 {
+        levenshtein_distance(s, t) as f64
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+/// `damerau_levenshtein_distance` as a `DistanceMetric`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DamerauMetric;
+
+impl DistanceMetric for DamerauMetric {
+    fn distance(&self, s: &str, t: &str) -> f64 {
+        damerau_levenshtein_distance(s, t) as f64
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+/// `osa_distance` as a `DistanceMetric`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OsaMetric;
+
+impl DistanceMetric for OsaMetric {
+    fn distance(&self, s: &str, t: &str) -> f64 {
+        osa_distance(s, t) as f64
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+/// `k_edit_distance` as a `DistanceMetric`, this one is already a score from 0.0 to 1.0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KEditMetric;
+
+impl DistanceMetric for KEditMetric {
+    fn distance(&self, s: &str, t: &str) -> f64 {
+        k_edit_distance(s, t) as f64
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+/// Wraps a `DistanceMetric` and remembers the last `capacity` pairs of strings it was asked
+/// about, for when the same pairs come up over and over like popular search terms. Once it's
+/// full the pair that was used longest ago is forgotten. For a symmetric metric `(s, t)` and
+/// `(t, s)` share an entry.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{CachedDistance, LevenshteinMetric};
+///
+/// let mut cached = CachedDistance::new(LevenshteinMetric, 100);
+/// assert_eq!(cached.distance("kitten", "sitting"), 3.0);
+/// // Only computed once
+/// assert_eq!(cached.distance("sitting", "kitten"), 3.0);
+/// assert_eq!(cached.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedDistance<M> {
+    metric: M,
+    capacity: usize,
+    /// Each pair's distance and when it was last used
+    entries: BTreeMap<(String, String), (f64, u64)>,
+    /// The pairs by when they were last used, oldest first
+    recency: BTreeMap<u64, (String, String)>,
+    tick: u64,
+}
+
+impl<M: DistanceMetric> CachedDistance<M> {
+    /// Caches up to `capacity` pairs, 0 doesn't cache anything.
+    pub fn new(metric: M, capacity: usize) -> Self {
+        Self {
+            metric,
+            capacity,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// The same as the wrapped metric's `distance`, from the cache if it's there.
+    pub fn distance(&mut self, s: &str, t: &str) -> f64 {
+        let key = if self.metric.is_symmetric() && t < s {
+            (t.to_string(), s.to_string())
+        } else {
+            (s.to_string(), t.to_string())
+        };
+        self.tick += 1;
+
+        if let Some((distance, last_used)) = self.entries.get_mut(&key) {
+            let key = self.recency.remove(last_used).unwrap();
+            *last_used = self.tick;
+            self.recency.insert(self.tick, key);
+            return *distance;
+        }
+
+        let distance = self.metric.distance(s, t);
+        if self.capacity == 0 {
+            return distance;
+        }
+        if self.entries.len() == self.capacity {
+            let (_, oldest) = self.recency.pop_first().unwrap();
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key.clone(), (distance, self.tick));
+        self.recency.insert(self.tick, key);
+
+        distance
+    }
+
+    /// How many pairs are cached
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// If nothing is cached yet
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The most pairs that will be cached
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_distance_metric() {
+        let metrics: Vec<Box<dyn DistanceMetric>> = vec![
+            Box::new(LevenshteinMetric),
+            Box::new(DamerauMetric),
+            Box::new(OsaMetric),
+            Box::new(KEditMetric),
+        ];
+
+        let distances: Vec<f64> = metrics.iter().map(|m| m.distance("ca", "abc")).collect();
+        assert_eq!(
+            distances,
+            vec![3.0, 2.0, 3.0, k_edit_distance("ca", "abc") as f64]
+        );
+
+        for metric in &metrics {
+            assert_eq!(metric.distance("국어", "국어"), 0.0);
+        }
+    }
+
+    /// Counts how many times it was actually asked for a distance
+    struct Counting<'a> {
+        calls: &'a core::cell::Cell<usize>,
+        symmetric: bool,
+    }
+
+    impl DistanceMetric for Counting<'_> {
+        fn distance(&self, s: &str, t: &str) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            // Not symmetric on purpose
+            s.len() as f64 - t.len() as f64
+        }
+
+        fn is_symmetric(&self) -> bool {
+            self.symmetric
+        }
+    }
+
+    #[test]
+    fn test_cached_distance() {
+        let words = ["국어", "숙어", "kitten", "sitting", "", "a"];
+        let mut cached = CachedDistance::new(KEditMetric, 100);
+        for _ in 0..2 {
+            for s in words {
+                for t in words {
+                    assert_eq!(cached.distance(s, t), KEditMetric.distance(s, t));
+                }
+            }
+        }
+        // Every unordered pair, including each word with itself
+        assert_eq!(cached.len(), 6 * 7 / 2);
+
+        let calls = core::cell::Cell::new(0);
+        let mut asymmetric = CachedDistance::new(
+            Counting {
+                calls: &calls,
+                symmetric: false,
+            },
+            10,
+        );
+        assert_eq!(asymmetric.distance("abc", "a"), 2.);
+        assert_eq!(asymmetric.distance("a", "abc"), -2.);
+        assert_eq!(asymmetric.distance("abc", "a"), 2.);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(asymmetric.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_distance_capacity() {
+        let calls = core::cell::Cell::new(0);
+        let mut cached = CachedDistance::new(
+            Counting {
+                calls: &calls,
+                symmetric: true,
+            },
+            2,
+        );
+        cached.distance("a", "b");
+        cached.distance("c", "d");
+        // Makes a-b the most recently used so c-d goes first
+        cached.distance("b", "a");
+        assert_eq!(calls.get(), 2);
+        cached.distance("e", "f");
+        assert_eq!(cached.len(), 2);
+        assert_eq!(calls.get(), 3);
+
+        cached.distance("a", "b");
+        assert_eq!(calls.get(), 3);
+        cached.distance("c", "d");
+        assert_eq!(calls.get(), 4);
+        assert_eq!(cached.len(), cached.capacity());
+
+        let mut uncached = CachedDistance::new(LevenshteinMetric, 0);
+        assert_eq!(uncached.distance("kitten", "sitting"), 3.);
+        assert!(uncached.is_empty());
+    }
+}
@@ -0,0 +1,385 @@
+//! Similarities from how many n-grams (runs of `n` characters) two strings share, these don't
+//! care much about order so they hold up better than edit distance on longer text.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// How many times each n-gram of `n` `char`s shows up in `s`. A string shorter than `n` (or any
+/// string when `n` is 0) is one n-gram of the whole thing so it can still match itself.
+fn ngram_counts(s: &[char], n: usize) -> BTreeMap<&[char], usize> {
+    let mut counts = BTreeMap::new();
+    if n == 0 || s.len() < n {
+        counts.insert(s, 1);
+        return counts;
+    }
+
+    for gram in s.windows(n) {
+        *counts.entry(gram).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Sørensen-Dice coefficient over adjacent character bigrams, 1.0 for exactly the same and 0.0
+/// for no bigrams in common. Bigrams that show up more than once are counted as many times as
+/// they are shared.
+///
+/// Strings too short to have a bigram (empty or a single `char`) are 1.0 if they're equal and
+/// 0.0 otherwise.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::dice_coefficient("night", "nacht"), 0.25);
+/// assert_eq!(k_edit_distance::dice_coefficient("", ""), 1.0);
+/// ```
+pub fn dice_coefficient(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    let s_bigrams = ngram_counts(&s, 2);
+    let t_bigrams = ngram_counts(&t, 2);
+    let total: usize = s_bigrams.values().chain(t_bigrams.values()).sum();
+
+    let shared: usize = s_bigrams
+        .iter()
+        .map(|(gram, count)| (*count).min(*t_bigrams.get(gram).unwrap_or(&0)))
+        .sum();
+
+    2. * shared as f32 / total as f32
+}
+
+/// Jaccard similarity over the sets of character n-grams, how many n-grams are in both strings
+/// out of how many are in either. 1.0 for the same set of n-grams and 0.0 for none in common.
+///
+/// A string shorter than `n` counts as a single n-gram of the whole string, so it only matches
+/// itself instead of everything else with no n-grams.
+///
+/// # Examples
+///
+/// ```
+/// // {ni, ig, gh, ht} and {na, ac, ch, ht} share one of seven
+/// assert_eq!(k_edit_distance::jaccard_ngram("night", "nacht", 2), 1.0 / 7.0);
+/// assert_eq!(k_edit_distance::jaccard_ngram("ab", "ab", 3), 1.0);
+/// ```
+pub fn jaccard_ngram(s: impl AsRef<str>, t: impl AsRef<str>, n: usize) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    let s_grams = ngram_counts(&s, n);
+    let t_grams = ngram_counts(&t, n);
+    let shared = shared_grams(&s_grams, &t_grams);
+    let either = s_grams.len() + t_grams.len() - shared;
+
+    shared as f32 / either as f32
+}
+
+/// The overlap (Szymkiewicz-Simpson) coefficient over the sets of character n-grams, how many
+/// n-grams are in both strings out of how many are in the one with fewer. Anything whose n-grams
+/// are all in the other string is 1.0, so a short query scores well against a long candidate
+/// that contains it.
+///
+/// Strings shorter than `n` are a single n-gram of the whole string like in `jaccard_ngram`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::overlap_coefficient("night", "nightly", 2), 1.0);
+/// assert_eq!(k_edit_distance::overlap_coefficient("night", "nacht", 2), 0.25);
+/// ```
+pub fn overlap_coefficient(s: impl AsRef<str>, t: impl AsRef<str>, n: usize) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    let s_grams = ngram_counts(&s, n);
+    let t_grams = ngram_counts(&t, n);
+    let shared = shared_grams(&s_grams, &t_grams);
+
+    shared as f32 / s_grams.len().min(t_grams.len()) as f32
+}
+
+/// The Tversky index over the sets of character n-grams, `shared / (shared + alpha * only_s +
+/// beta * only_t)` where `only_s` and `only_t` are the n-grams in just one of the strings. An
+/// `alpha` and `beta` of 0.5 are the Dice coefficient and 1.0 are Jaccard, a small `beta` means
+/// n-grams missing from `s` (the query) hardly count against a longer `t` (the candidate).
+///
+/// Strings shorter than `n` are a single n-gram of the whole string like in `jaccard_ngram`.
+/// Nothing shared with `alpha` and `beta` both 0 is 0.0.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{jaccard_ngram, tversky_index};
+///
+/// assert_eq!(tversky_index("night", "nacht", 2, 1., 1.), jaccard_ngram("night", "nacht", 2));
+/// // {ni, ig, gh, ht} are all in "nightly"
+/// assert_eq!(tversky_index("night", "nightly", 2, 1., 0.), 1.0);
+/// ```
+pub fn tversky_index(
+    s: impl AsRef<str>,
+    t: impl AsRef<str>,
+    n: usize,
+    alpha: f32,
+    beta: f32,
+) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    let s_grams = ngram_counts(&s, n);
+    let t_grams = ngram_counts(&t, n);
+    let shared = shared_grams(&s_grams, &t_grams) as f32;
+    let only_s = s_grams.len() as f32 - shared;
+    let only_t = t_grams.len() as f32 - shared;
+
+    let total = shared + alpha * only_s + beta * only_t;
+    if total == 0. {
+        return 0.;
+    }
+    shared / total
+}
+
+/// How many distinct n-grams are in both sets of counts
+fn shared_grams(s_grams: &BTreeMap<&[char], usize>, t_grams: &BTreeMap<&[char], usize>) -> usize {
+    s_grams
+        .keys()
+        .filter(|gram| t_grams.contains_key(*gram))
+        .count()
+}
+
+/// The q-gram distance, how many q-grams of `q` characters you'd have to add to or remove from
+/// one string's q-grams to get the other's. Both strings are padded with `q - 1` NUL characters
+/// at each end first so short strings still have q-grams and the first and last characters
+/// count as much as the rest. A `q` of 0 is treated as 1.
+///
+/// It only compares how often each q-gram shows up, so different strings made of the same
+/// q-grams in another order are 0 apart, like "aaba" and "abaa" with `q` of 2. That makes it an
+/// approximation, but a cheap one that never says more than `2 * q` times the Levenshtein
+/// distance, so `levenshtein_distance(s, t) >= qgram_distance(s, t, q) / (2 * q)` can rule out
+/// candidates before computing the real distance.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::qgram_distance("night", "nacht", 2), 6);
+/// assert_eq!(k_edit_distance::qgram_distance("aaba", "abaa", 2), 0);
+/// ```
+pub fn qgram_distance(s: impl AsRef<str>, t: impl AsRef<str>, q: usize) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let q = q.max(1);
+    let padded = |s: &str| -> Vec<char> {
+        let padding = vec!['\0'; q - 1];
+        padding
+            .iter()
+            .copied()
+            .chain(s.chars())
+            .chain(padding.iter().copied())
+            .collect()
+    };
+    let s = padded(s);
+    let t = padded(t);
+
+    let mut s_grams = ngram_counts(&s, q);
+    let mut t_grams = ngram_counts(&t, q);
+    // An empty string with no padding would otherwise count as one empty gram
+    for grams in [&mut s_grams, &mut t_grams] {
+        grams.retain(|gram, _| gram.len() == q);
+    }
+
+    let only_t: usize = t_grams
+        .iter()
+        .filter(|(gram, _)| !s_grams.contains_key(*gram))
+        .map(|(_, count)| count)
+        .sum();
+    s_grams
+        .iter()
+        .map(|(gram, count)| count.abs_diff(*t_grams.get(gram).unwrap_or(&0)))
+        .sum::<usize>()
+        + only_t
+}
+
+/// Cosine similarity between how often each character n-gram of `n` shows up in `s` and `t`,
+/// 1.0 for the same mix of n-grams whatever the lengths and 0.0 for none in common. Like
+/// `jaccard_ngram` a string shorter than `n` is a single n-gram of the whole string, except an
+/// empty string has no n-grams at all and is 0.0 against anything, itself included.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::cosine_ngram("night", "night", 2), 1.0);
+/// // Twice as long with almost the same mix of bigrams
+/// assert_eq!(k_edit_distance::cosine_ngram("abab", "abababab", 2), 0.9838699);
+/// assert_eq!(k_edit_distance::cosine_ngram("abc", "xyz", 2), 0.0);
+/// ```
+pub fn cosine_ngram(s: impl AsRef<str>, t: impl AsRef<str>, n: usize) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+    if s.is_empty() || t.is_empty() {
+        return 0.;
+    }
+
+    let s_grams = ngram_counts(&s, n);
+    let t_grams = ngram_counts(&t, n);
+    let dot: usize = s_grams
+        .iter()
+        .map(|(gram, count)| count * t_grams.get(gram).unwrap_or(&0))
+        .sum();
+    let norm = |grams: &BTreeMap<&[char], usize>| grams.values().map(|c| c * c).sum::<usize>();
+
+    (dot as f64 / sqrt((norm(&s_grams) * norm(&t_grams)) as f64)).min(1.) as f32
+}
+
+/// Newton's method since `f64::sqrt` needs std, only for the positive whole numbers
+/// `cosine_ngram` needs it for
+fn sqrt(x: f64) -> f64 {
+    // Starting above the root it only ever goes down until it can't get any closer
+    let mut root = x.max(1.);
+    loop {
+        let next = 0.5 * (root + x / root);
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dice_coefficient() {
+        assert_eq!(dice_coefficient("night", "nacht"), 0.25);
+        assert_eq!(dice_coefficient("night", "night"), 1.);
+        assert_eq!(dice_coefficient("", ""), 1.);
+        assert_eq!(dice_coefficient("a", "a"), 1.);
+        assert_eq!(dice_coefficient("a", "b"), 0.);
+        assert_eq!(dice_coefficient("a", ""), 0.);
+        assert_eq!(dice_coefficient("a", "ab"), 0.);
+        assert_eq!(dice_coefficient("abc", "xyz"), 0.);
+        // "aa" is shared once, not twice
+        assert_eq!(dice_coefficient("aaa", "aa"), 2. / 3.);
+        assert_eq!(dice_coefficient("aaaa", "aaa"), 4. / 5.);
+        assert_eq!(dice_coefficient("국어사전", "국어"), 2. / 4.);
+    }
+
+    #[test]
+    fn test_jaccard_ngram() {
+        assert_eq!(jaccard_ngram("night", "night", 2), 1.);
+        assert_eq!(jaccard_ngram("night", "night", 3), 1.);
+        assert_eq!(jaccard_ngram("night", "nacht", 2), 1. / 7.);
+        // {nig, igh, ght} and {nac, ach, cht}
+        assert_eq!(jaccard_ngram("night", "nacht", 3), 0.);
+        // {abc, bcd} and {bcd, cde}
+        assert_eq!(jaccard_ngram("abcd", "bcde", 3), 1. / 3.);
+        // Repeats only count once, {ab, ba}
+        assert_eq!(jaccard_ngram("abab", "ab", 2), 1. / 2.);
+        assert_eq!(jaccard_ngram("abab", "baba", 2), 1.);
+        // Shorter than n
+        assert_eq!(jaccard_ngram("ab", "ab", 3), 1.);
+        assert_eq!(jaccard_ngram("ab", "ba", 3), 0.);
+        assert_eq!(jaccard_ngram("ab", "abc", 3), 0.);
+        assert_eq!(jaccard_ngram("", "", 2), 1.);
+        assert_eq!(jaccard_ngram("", "a", 2), 0.);
+        assert_eq!(jaccard_ngram("abc", "abc", 0), 1.);
+        assert_eq!(jaccard_ngram("국어사전", "국어", 2), 1. / 3.);
+    }
+
+    #[test]
+    fn test_overlap_coefficient() {
+        assert_eq!(overlap_coefficient("night", "night", 2), 1.);
+        assert_eq!(overlap_coefficient("night", "nightly", 2), 1.);
+        assert_eq!(overlap_coefficient("nightly", "night", 2), 1.);
+        assert_eq!(overlap_coefficient("night", "nacht", 2), 1. / 4.);
+        assert_eq!(overlap_coefficient("abc", "xyz", 2), 0.);
+        assert_eq!(overlap_coefficient("국어", "국어사전", 2), 1.);
+        // Shorter than n
+        assert_eq!(overlap_coefficient("ab", "ab", 3), 1.);
+        assert_eq!(overlap_coefficient("ab", "abc", 3), 0.);
+        assert_eq!(overlap_coefficient("", "", 2), 1.);
+    }
+
+    #[test]
+    fn test_tversky_index() {
+        // None of these repeat a bigram so the sets are the same as the counts Dice uses
+        for (s, t) in [
+            ("night", "nacht"),
+            ("night", "nightly"),
+            ("abc", "xyz"),
+            ("국어사전", "국어"),
+            ("kitten", "sitting"),
+        ] {
+            assert_eq!(tversky_index(s, t, 2, 0.5, 0.5), dice_coefficient(s, t));
+            assert_eq!(tversky_index(s, t, 2, 1., 1.), jaccard_ngram(s, t, 2));
+            assert_eq!(tversky_index(s, t, 3, 1., 1.), jaccard_ngram(s, t, 3));
+        }
+
+        assert_eq!(tversky_index("night", "nightly", 2, 1., 0.), 1.);
+        // The two missing bigrams of "nightly" count for half as much
+        assert_eq!(tversky_index("night", "nightly", 2, 1., 0.5), 4. / 5.);
+        assert_eq!(tversky_index("abc", "xyz", 2, 0., 0.), 0.);
+    }
+
+    #[test]
+    fn test_qgram_distance() {
+        assert_eq!(qgram_distance("night", "night", 2), 0);
+        assert_eq!(qgram_distance("", "", 3), 0);
+        // {\0n, ni, ig, gh, ht, t\0} against {\0n, na, ac, ch, ht, t\0}
+        assert_eq!(qgram_distance("night", "nacht", 2), 6);
+        // {\0a, a\0} against {\0\0}
+        assert_eq!(qgram_distance("a", "", 2), 3);
+        assert_eq!(qgram_distance("a", "b", 1), 2);
+        assert_eq!(qgram_distance("ab", "ba", 1), 0);
+        assert_eq!(qgram_distance("ab", "ba", 0), 0);
+        // Same q-grams in a different order
+        assert_eq!(qgram_distance("aaba", "abaa", 2), 0);
+        assert_eq!(crate::levenshtein_distance("aaba", "abaa"), 2);
+
+        for (s, t) in [
+            ("kitten", "sitting"),
+            ("국어사전", "국어"),
+            ("abc", "xyz"),
+            ("", "abc"),
+        ] {
+            for q in 1..=3 {
+                assert!(crate::levenshtein_distance(s, t) >= qgram_distance(s, t, q) / (2 * q));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cosine_ngram() {
+        assert_eq!(cosine_ngram("night", "night", 2), 1.);
+        assert_eq!(cosine_ngram("국어사전", "국어사전", 3), 1.);
+        assert_eq!(cosine_ngram("abc", "abc", 0), 1.);
+        // Anagrams with different bigrams
+        assert_eq!(cosine_ngram("abcd", "dcba", 2), 0.);
+        assert!(cosine_ngram("listen", "silent", 2) < 1.);
+        assert!(cosine_ngram("listen", "silent", 2) > 0.);
+        // Same characters so unigrams can't tell them apart
+        assert_eq!(cosine_ngram("listen", "silent", 1), 1.);
+        // {ni, ig, gh, ht} and {na, ac, ch, ht}
+        assert_eq!(cosine_ngram("night", "nacht", 2), 0.25);
+        // Shorter than n
+        assert_eq!(cosine_ngram("ab", "ab", 3), 1.);
+        assert_eq!(cosine_ngram("ab", "abc", 3), 0.);
+        // Nothing to compare
+        assert_eq!(cosine_ngram("", "", 2), 0.);
+        assert_eq!(cosine_ngram("", "abc", 2), 0.);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for x in [1., 4., 9., 144., 1e12] {
+            assert_eq!(sqrt(x), x.sqrt());
+        }
+        // Can be a bit off in f64 but never enough to change the f32 similarity
+        for x in [2., 3., 10., 12345.] {
+            assert_eq!(sqrt(x) as f32, x.sqrt() as f32);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Codes for how English words sound, the counterpart to the consonant folding `normalize` does
+//! for Korean.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The American Soundex code of `s`, its first letter followed by three digits for the
+/// consonants after it, like "R163" for both "Robert" and "Rupert". Anything that isn't an
+/// ASCII letter is ignored and a string with no letters gives an empty code.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::soundex("Robert"), "R163");
+/// assert_eq!(k_edit_distance::soundex("Tymczak"), "T522");
+/// assert_eq!(k_edit_distance::soundex("Lee"), "L000");
+/// ```
+pub fn soundex(s: &str) -> String {
+    let mut letters = s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase());
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut code = String::from(first);
+    let mut last_digit = soundex_digit(first);
+    for letter in letters.filter(|letter| !matches!(letter, 'H' | 'W')) {
+        match soundex_digit(letter) {
+            Some(digit) if Some(digit) != last_digit => {
+                code.push(digit);
+                if code.len() == 4 {
+                    return code;
+                }
+                last_digit = Some(digit);
+            }
+            Some(_) => {}
+            // A vowel between two letters with the same digit codes both of them, H and W
+            // (skipped above) don't
+            None => last_digit = None,
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// Whether `s` and `t` have the same `soundex` code. Two strings without any letters don't
+/// sound alike.
+///
+/// # Examples
+///
+/// ```
+/// assert!(k_edit_distance::soundex_equal("Robert", "Rupert"));
+/// assert!(!k_edit_distance::soundex_equal("Robert", "Rubin"));
+/// ```
+pub fn soundex_equal(s: impl AsRef<str>, t: impl AsRef<str>) -> bool {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s = soundex(s);
+    !s.is_empty() && s == soundex(t)
+}
+
+fn soundex_digit(letter: char) -> Option<char> {
+    match letter {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+/// The Metaphone code of `s`, Lawrence Philips' original rules for how English is spelled
+/// versus said, like "NT" for both "night" and "nite". It keeps more of the word than
+/// `soundex` and isn't cut to a fixed length. Anything that isn't an ASCII letter is ignored.
+///
+/// The code uses 0 for "th" and X for "sh", vowels are only kept at the start. On top of the
+/// original rules "th" before "om" or "am" is T like in Double Metaphone (Thomas, Thompson)
+/// and the P in "mps" is silent.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::metaphone("Thompson"), "TMSN");
+/// assert_eq!(k_edit_distance::metaphone("Knight"), "NT");
+/// assert_eq!(k_edit_distance::metaphone("Smith"), "SM0");
+/// ```
+pub fn metaphone(s: &str) -> String {
+    let mut word: Vec<char> = Vec::new();
+    for letter in s.chars().filter(char::is_ascii_alphabetic) {
+        let letter = letter.to_ascii_uppercase();
+        // Double letters sound like one, except CC like in "accept"
+        if word.last() != Some(&letter) || letter == 'C' {
+            word.push(letter);
+        }
+    }
+
+    match word.get(..2) {
+        Some(['A', 'E'] | ['G', 'N'] | ['K', 'N'] | ['P', 'N'] | ['W', 'R']) => {
+            word.remove(0);
+        }
+        Some(['W', 'H']) => {
+            word.remove(1);
+        }
+        _ => {}
+    }
+    if word.first() == Some(&'X') {
+        word[0] = 'S';
+    }
+
+    let is_vowel = |c: Option<&char>| matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'));
+    let is_front_vowel = |c: Option<&char>| matches!(c, Some('E' | 'I' | 'Y'));
+    let mut code = String::new();
+    for (i, &letter) in word.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|i| word[i]);
+        let next = word.get(i + 1);
+        let after_next = word.get(i + 2);
+        let is_last = i + 1 == word.len();
+
+        match letter {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(letter);
+                }
+            }
+            // Silent at the end of "-mb" like "dumb"
+            'B' if prev == Some('M') && is_last => {}
+            'C' if prev == Some('S') && is_front_vowel(next) => {}
+            'C' if next == Some(&'I') && after_next == Some(&'A') => code.push('X'),
+            'C' if next == Some(&'H') => code.push(if prev == Some('S') { 'K' } else { 'X' }),
+            'C' if is_front_vowel(next) => code.push('S'),
+            'C' => code.push('K'),
+            'D' if next == Some(&'G') && is_front_vowel(after_next) => code.push('J'),
+            'D' => code.push('T'),
+            'G' if next == Some(&'H') && !(after_next.is_none() || is_vowel(after_next)) => {}
+            'G' if next == Some(&'N') && matches!(&word[i + 2..], [] | ['E', 'D']) => {}
+            'G' if prev == Some('D') && is_front_vowel(next) => {}
+            'G' if is_front_vowel(next) => code.push('J'),
+            'G' => code.push('K'),
+            'H' if matches!(prev, Some('C' | 'G' | 'P' | 'S' | 'T')) => {}
+            'H' if is_vowel(prev.as_ref()) && !is_vowel(next) => {}
+            'K' if prev == Some('C') => {}
+            'P' if next == Some(&'H') => code.push('F'),
+            'P' if prev == Some('M') && next == Some(&'S') => {}
+            'Q' => code.push('K'),
+            'S' if next == Some(&'H') => code.push('X'),
+            'S' if next == Some(&'I') && matches!(after_next, Some('O' | 'A')) => code.push('X'),
+            'T' if next == Some(&'I') && matches!(after_next, Some('O' | 'A')) => code.push('X'),
+            'T' if next == Some(&'H') => {
+                let thomas = matches!(word.get(i + 2..i + 4), Some(['O' | 'A', 'M']));
+                code.push(if thomas { 'T' } else { '0' });
+            }
+            'T' if next == Some(&'C') && after_next == Some(&'H') => {}
+            'V' => code.push('F'),
+            'W' | 'Y' if !is_vowel(next) => {}
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(letter),
+        }
+    }
+
+    code
+}
+
+/// Whether `s` and `t` have the same `metaphone` code. Two strings without any letters don't
+/// sound alike.
+///
+/// # Examples
+///
+/// ```
+/// assert!(k_edit_distance::metaphone_equal("night", "nite"));
+/// assert!(!k_edit_distance::metaphone_equal("night", "knife"));
+/// ```
+pub fn metaphone_equal(s: impl AsRef<str>, t: impl AsRef<str>) -> bool {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s = metaphone(s);
+    !s.is_empty() && s == metaphone(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        for (name, code) in [
+            ("Robert", "R163"),
+            ("Rupert", "R163"),
+            ("Rubin", "R150"),
+            ("Ashcraft", "A261"),
+            ("Ashcroft", "A261"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("Lee", "L000"),
+            ("Gutierrez", "G362"),
+            ("Jackson", "J250"),
+            ("VanDeusen", "V532"),
+        ] {
+            assert_eq!(soundex(name), code, "{name}");
+        }
+
+        assert_eq!(soundex("robert"), "R163");
+        assert_eq!(soundex("O'Hara"), "O600");
+        assert_eq!(soundex("  Lee! "), "L000");
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("123 국어"), "");
+    }
+
+    #[test]
+    fn test_soundex_equal() {
+        assert!(soundex_equal("Robert", "Rupert"));
+        assert!(soundex_equal("Ashcraft", "ashcroft"));
+        assert!(!soundex_equal("Robert", "Rubin"));
+        assert!(!soundex_equal("", ""));
+        assert!(!soundex_equal("국어", "숙어"));
+    }
+
+    #[test]
+    fn test_metaphone() {
+        for (word, code) in [
+            ("Thompson", "TMSN"),
+            ("Thomas", "TMS"),
+            ("Thumb", "0M"),
+            ("Smith", "SM0"),
+            ("night", "NT"),
+            ("nite", "NT"),
+            ("Knight", "NT"),
+            ("Wright", "RT"),
+            ("Gnome", "NM"),
+            ("Aeon", "EN"),
+            ("Xavier", "SFR"),
+            ("White", "WT"),
+            ("Philip", "FLP"),
+            ("Cherry", "XR"),
+            ("School", "SKL"),
+            ("Science", "SNS"),
+            ("Judge", "JJ"),
+            ("Nation", "NXN"),
+            ("Ghost", "KST"),
+            ("sign", "SN"),
+            ("Dumb", "TM"),
+            ("Box", "BKS"),
+            ("Aubrey", "ABR"),
+            ("Zack", "SK"),
+            ("Matthew", "M0"),
+        ] {
+            assert_eq!(metaphone(word), code, "{word}");
+        }
+
+        assert_eq!(metaphone("thompson"), "TMSN");
+        assert_eq!(metaphone("O'Neil"), "ONL");
+        assert_eq!(metaphone(""), "");
+        assert_eq!(metaphone("123 국어"), "");
+    }
+
+    #[test]
+    fn test_metaphone_equal() {
+        assert!(metaphone_equal("night", "nite"));
+        assert!(metaphone_equal("Philip", "Filip"));
+        assert!(metaphone_equal("Smith", "Smyth"));
+        assert!(!metaphone_equal("Smith", "Schmidt"));
+        assert!(!metaphone_equal("", ""));
+        assert!(!metaphone_equal("국어", "숙어"));
+    }
+}
@@ -0,0 +1,438 @@
+//! Writing Korean in Latin letters so it can be compared against romanized text.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{decompose_syllable, levenshtein_ratio};
+
+/// Initial consonants in the order of U+1100 to U+1112, ㅇ is silent at the start of a syllable
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+/// Vowels in the order of U+1161 to U+1175
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+/// Final consonants as they sound at the end of a syllable, in the order of U+11A8 to U+11C2
+/// with no final consonant first
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// Final consonants followed by a syllable starting with a silent ㅇ, split into what is left
+/// at the end of the syllable and what is carried over to start the next one
+const LINKED: [(&str, &str); 28] = [
+    ("", ""),
+    ("", "g"),
+    ("", "kk"),
+    ("k", "s"),
+    ("", "n"),
+    ("n", "j"),
+    ("", "n"),
+    ("", "d"),
+    ("", "r"),
+    ("l", "g"),
+    ("l", "m"),
+    ("l", "b"),
+    ("l", "s"),
+    ("l", "t"),
+    ("l", "p"),
+    ("", "r"),
+    ("", "m"),
+    ("", "b"),
+    ("p", "s"),
+    ("", "s"),
+    ("", "ss"),
+    ("ng", ""),
+    ("", "j"),
+    ("", "ch"),
+    ("", "k"),
+    ("", "t"),
+    ("", "p"),
+    ("", ""),
+];
+
+/// Initial consonants in McCune-Reischauer, where ㄱ ㄷ ㅂ ㅈ are voiced to g d b j between
+/// voiced sounds
+const MR_INITIALS: [&str; 19] = [
+    "k", "kk", "n", "t", "tt", "r", "m", "p", "pp", "s", "ss", "", "ch", "tch", "ch'", "k'", "t'",
+    "p'", "h",
+];
+
+/// Vowels in McCune-Reischauer
+const MR_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "ŏ", "e", "yŏ", "ye", "o", "wa", "wae", "oe", "yo", "u", "wŏ", "we",
+    "wi", "yu", "ŭ", "ŭi", "i",
+];
+
+const INITIAL_G: usize = 0;
+const INITIAL_N: usize = 2;
+const INITIAL_D: usize = 3;
+const INITIAL_R: usize = 5;
+const INITIAL_M: usize = 6;
+const INITIAL_SILENT: usize = 11;
+const INITIAL_J: usize = 12;
+const FINAL_NH: usize = 6;
+const FINAL_LH: usize = 15;
+const FINAL_H: usize = 27;
+
+/// `s` in the Revised Romanization of Korean, the official system since 2000, like "서울" to
+/// "seoul". Anything that isn't a Hangul syllable is kept as is.
+///
+/// Between syllables in the same word it follows how the consonants are pronounced:
+///
+/// - a final consonant before a silent ㅇ moves over to the next syllable, 한국어 is "hangugeo"
+/// - ㄱ ㄷ ㅂ before ㄴ or ㅁ turn nasal, 합니다 is "hamnida"
+/// - ㄹ after anything but ㄴ or ㄹ is read as ㄴ, 종로 is "jongno" and 독립 is "dongnip"
+/// - ㄴ and ㄹ next to each other become "ll", 신라 is "silla"
+/// - ㅎ followed by ㄱ ㄷ ㅈ aspirates them, 좋고 is "joko"
+///
+/// Palatalization (같이 as "gachi"), ㄱ ㄷ ㅂ followed by ㅎ and the exceptions that depend on
+/// what a word means aren't handled, those come out as the plain spelling. Proper nouns aren't
+/// capitalized and no hyphens are added.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{levenshtein_distance, revised_romanize};
+///
+/// assert_eq!(revised_romanize("한국"), "hanguk");
+/// assert_eq!(levenshtein_distance(&revised_romanize("서울"), "seoul"), 0);
+/// ```
+pub fn revised_romanize(s: &str) -> String {
+    romanize(s, Scheme::Revised(Devoicing::None))
+}
+
+/// `s` in McCune-Reischauer, the romanization libraries and a lot of older books use, like
+/// "부산" to "pusan". Anything that isn't a Hangul syllable is kept as is.
+///
+/// It follows the same sound changes between syllables as `revised_romanize` but spells them
+/// the McCune-Reischauer way:
+///
+/// - ㄱ ㄷ ㅂ ㅈ are k t p ch, except between voiced sounds where they're g d b j, so 부산 is
+///   "pusan" and 학교 is "hakkyo" but 한국어 is "han'gugŏ"
+/// - ㅓ and ㅡ are "ŏ" and "ŭ" with a breve, 서울 is "sŏul"
+/// - aspirated consonants have an apostrophe, 김치 is "kimch'i"
+/// - an apostrophe also goes between n and g when they're separate sounds, 한글 is "han'gŭl"
+///   which would be read 항을 without it
+///
+/// Like `revised_romanize` proper nouns aren't capitalized and no hyphens are added.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::mccune_reischauer_romanize;
+///
+/// assert_eq!(mccune_reischauer_romanize("부산"), "pusan");
+/// assert_eq!(mccune_reischauer_romanize("독립"), "tongnip");
+/// ```
+pub fn mccune_reischauer_romanize(s: &str) -> String {
+    romanize(s, Scheme::McCuneReischauer)
+}
+
+/// The romanizations `cross_script_distance_with` can compare against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RomanizationScheme {
+    /// `revised_romanize`, along with the older k t p ch spellings of ㄱ ㄷ ㅂ ㅈ
+    #[default]
+    Revised,
+    /// `mccune_reischauer_romanize`, with or without the breves and apostrophes
+    McCuneReischauer,
+}
+
+/// Older romanizations (McCune-Reischauer and the spellings people learned from it) write
+/// ㄱ ㄷ ㅂ ㅈ as k t p ch where Revised Romanization has g d b j, at least at the start of a
+/// word like "Pusan" for 부산
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Devoicing {
+    None,
+    WordInitial,
+    All,
+}
+
+/// Which romanization `romanize` writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scheme {
+    Revised(Devoicing),
+    McCuneReischauer,
+}
+
+fn romanize(s: &str, scheme: Scheme) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let jamo: Vec<Option<(usize, usize, usize)>> = chars.iter().map(|c| jamo_indices(*c)).collect();
+
+    let mut romanized = String::new();
+    let mut carried_initial = None;
+    let mut previous_ending = "";
+    for (i, c) in chars.iter().enumerate() {
+        let Some((initial, vowel, final_consonant)) = jamo[i] else {
+            romanized.push(*c);
+            carried_initial = None;
+            continue;
+        };
+
+        let word_initial = i == 0 || jamo[i - 1].is_none();
+        let (initial, vowel) = match scheme {
+            Scheme::Revised(devoicing) => {
+                let devoiced = match devoicing {
+                    Devoicing::None => false,
+                    Devoicing::WordInitial => word_initial,
+                    Devoicing::All => true,
+                };
+                let initial = match INITIALS[initial] {
+                    "g" if devoiced => "k",
+                    "d" if devoiced => "t",
+                    "b" if devoiced => "p",
+                    "j" if devoiced => "ch",
+                    plain => plain,
+                };
+                let initial = carried_initial.take().unwrap_or(initial);
+                (initial, VOWELS[vowel])
+            }
+            Scheme::McCuneReischauer => {
+                let voiced = !word_initial && !previous_ending.ends_with(['k', 't', 'p']);
+                let initial = match MR_INITIALS[initial] {
+                    "k" if voiced => "g",
+                    "t" if voiced => "d",
+                    "p" if voiced => "b",
+                    "ch" if voiced => "j",
+                    plain => plain,
+                };
+                // What gets carried over is spelled for Revised Romanization, where the
+                // aspirated consonants are the ones left unvoiced
+                let initial = match carried_initial.take() {
+                    Some("k") => "k'",
+                    Some("t") => "t'",
+                    Some("p") => "p'",
+                    Some("ch") => "ch'",
+                    Some(carried) => carried,
+                    None => initial,
+                };
+                if !word_initial && previous_ending.ends_with('n') && initial.starts_with('g') {
+                    romanized.push('\'');
+                }
+                (initial, MR_VOWELS[vowel])
+            }
+        };
+        romanized.push_str(initial);
+        romanized.push_str(vowel);
+
+        let next_initial = jamo.get(i + 1).copied().flatten().map(|(next, _, _)| next);
+        let (ending, next) = final_before(final_consonant, next_initial);
+        romanized.push_str(ending);
+        carried_initial = next;
+        previous_ending = ending;
+    }
+
+    romanized
+}
+
+/// How far apart `korean` is from romanized text the user typed in `latin`, 0.0 for the same
+/// and 1.0 for nothing in common. `korean` goes through `revised_romanize` and is compared to
+/// `latin` (lower cased) by Levenshtein distance divided by the longer length.
+///
+/// Since lots of people still write ㄱ ㄷ ㅂ ㅈ as k t p ch, the romanization is also tried with
+/// them like that at the start of each word and everywhere, whichever of the three is closest
+/// counts. That way both "busan" and "pusan" match 부산.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::cross_script_distance;
+///
+/// assert_eq!(cross_script_distance("서울", "seoul"), 0.0);
+/// assert_eq!(cross_script_distance("부산", "Pusan"), 0.0);
+/// assert!(cross_script_distance("서울", "busan") > 0.5);
+/// ```
+pub fn cross_script_distance(korean: &str, latin: &str) -> f32 {
+    cross_script_distance_with(korean, latin, RomanizationScheme::Revised)
+}
+
+/// `cross_script_distance` romanizing `korean` with `scheme`. With
+/// `RomanizationScheme::McCuneReischauer` it's compared both with and without the breves and
+/// apostrophes, since they're usually left out when typing, and whichever is closer counts.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::{cross_script_distance_with, RomanizationScheme};
+///
+/// let scheme = RomanizationScheme::McCuneReischauer;
+/// assert_eq!(cross_script_distance_with("서울", "Sŏul", scheme), 0.0);
+/// assert_eq!(cross_script_distance_with("서울", "soul", scheme), 0.0);
+/// assert!(cross_script_distance_with("서울", "seoul", scheme) > 0.0);
+/// ```
+pub fn cross_script_distance_with(korean: &str, latin: &str, scheme: RomanizationScheme) -> f32 {
+    let latin = latin.to_lowercase();
+    let romanizations = match scheme {
+        RomanizationScheme::Revised => [Devoicing::None, Devoicing::WordInitial, Devoicing::All]
+            .into_iter()
+            .map(|devoicing| romanize(korean, Scheme::Revised(devoicing)))
+            .collect(),
+        RomanizationScheme::McCuneReischauer => {
+            let romanized = mccune_reischauer_romanize(korean);
+            let plain = romanized
+                .chars()
+                .filter(|c| *c != '\'')
+                .map(|c| match c {
+                    'ŏ' => 'o',
+                    'ŭ' => 'u',
+                    c => c,
+                })
+                .collect();
+            alloc::vec![romanized, plain]
+        }
+    };
+    romanizations
+        .into_iter()
+        .map(|romanized: String| 1.0 - levenshtein_ratio(romanized, &latin))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// How `final_consonant` is written before a syllable starting with `next_initial`, along with
+/// how that initial is written if it changes
+fn final_before(
+    final_consonant: usize,
+    next_initial: Option<usize>,
+) -> (&'static str, Option<&'static str>) {
+    let sound = FINALS[final_consonant];
+    let Some(next_initial) = next_initial else {
+        return (sound, None);
+    };
+    if final_consonant == 0 {
+        return ("", None);
+    }
+
+    if matches!(final_consonant, FINAL_H | FINAL_NH | FINAL_LH) {
+        let aspirated = match next_initial {
+            INITIAL_G => Some("k"),
+            INITIAL_D => Some("t"),
+            INITIAL_J => Some("ch"),
+            _ => None,
+        };
+        if aspirated.is_some() {
+            let ending = if final_consonant == FINAL_H {
+                ""
+            } else {
+                sound
+            };
+            return (ending, aspirated);
+        }
+    }
+
+    match (next_initial, sound) {
+        (INITIAL_SILENT, _) => {
+            let (ending, carried) = LINKED[final_consonant];
+            (ending, Some(carried))
+        }
+        (INITIAL_N | INITIAL_M, "k") => ("ng", None),
+        (INITIAL_N | INITIAL_M, "t") => ("n", None),
+        (INITIAL_N | INITIAL_M, "p") => ("m", None),
+        (INITIAL_N, "l") | (INITIAL_R, "l" | "n") => ("l", Some("l")),
+        (INITIAL_R, "k") => ("ng", Some("n")),
+        (INITIAL_R, "t") => ("n", Some("n")),
+        (INITIAL_R, "p") => ("m", Some("n")),
+        (INITIAL_R, _) => (sound, Some("n")),
+        _ => (sound, None),
+    }
+}
+
+/// The positions of a syllable's jamo in `INITIALS`, `VOWELS` and `FINALS`
+fn jamo_indices(c: char) -> Option<(usize, usize, usize)> {
+    let (initial, vowel, final_consonant) = decompose_syllable(c)?;
+    Some((
+        (initial as u32 - 0x1100) as usize,
+        (vowel as u32 - 0x1161) as usize,
+        final_consonant.map_or(0, |c| (c as u32 - 0x11A7) as usize),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revised_romanize() {
+        assert_eq!(revised_romanize("서울"), "seoul");
+        assert_eq!(revised_romanize("한국"), "hanguk");
+        assert_eq!(revised_romanize("부산"), "busan");
+        assert_eq!(revised_romanize("라면"), "ramyeon");
+        assert_eq!(revised_romanize("한국어"), "hangugeo");
+        assert_eq!(revised_romanize("읽어"), "ilgeo");
+        assert_eq!(revised_romanize("좋아"), "joa");
+        assert_eq!(revised_romanize("합니다"), "hamnida");
+        assert_eq!(revised_romanize("백마"), "baengma");
+        assert_eq!(revised_romanize("종로"), "jongno");
+        assert_eq!(revised_romanize("독립"), "dongnip");
+        assert_eq!(revised_romanize("신라"), "silla");
+        assert_eq!(revised_romanize("설날"), "seollal");
+        assert_eq!(revised_romanize("좋고"), "joko");
+        assert_eq!(revised_romanize("않다"), "anta");
+        // rules only apply inside a word
+        assert_eq!(revised_romanize("한국 어"), "hanguk eo");
+        assert_eq!(revised_romanize("k-pop 노래"), "k-pop norae");
+        assert_eq!(revised_romanize(""), "");
+    }
+
+    #[test]
+    fn test_mccune_reischauer_romanize() {
+        assert_eq!(mccune_reischauer_romanize("부산"), "pusan");
+        assert_eq!(mccune_reischauer_romanize("서울"), "sŏul");
+        assert_eq!(mccune_reischauer_romanize("대구"), "taegu");
+        assert_eq!(mccune_reischauer_romanize("제주도"), "chejudo");
+        assert_eq!(mccune_reischauer_romanize("학교"), "hakkyo");
+        assert_eq!(mccune_reischauer_romanize("김치"), "kimch'i");
+        assert_eq!(mccune_reischauer_romanize("한글"), "han'gŭl");
+        assert_eq!(mccune_reischauer_romanize("한국어"), "han'gugŏ");
+        assert_eq!(mccune_reischauer_romanize("읽어"), "ilgŏ");
+        assert_eq!(mccune_reischauer_romanize("좋고"), "chok'o");
+        assert_eq!(mccune_reischauer_romanize("짜장"), "tchajang");
+        // Assimilation is the same as in Revised Romanization
+        assert_eq!(mccune_reischauer_romanize("독립"), "tongnip");
+        assert_eq!(mccune_reischauer_romanize("종로"), "chongno");
+        assert_eq!(mccune_reischauer_romanize("신라"), "silla");
+        assert_eq!(mccune_reischauer_romanize("합니다"), "hamnida");
+        assert_eq!(mccune_reischauer_romanize("한국 어"), "han'guk ŏ");
+        assert_eq!(mccune_reischauer_romanize(""), "");
+    }
+
+    #[test]
+    fn test_cross_script_distance_with() {
+        let mr = RomanizationScheme::McCuneReischauer;
+        assert_eq!(cross_script_distance_with("부산", "Pusan", mr), 0.);
+        assert_eq!(cross_script_distance_with("한글", "Han'gŭl", mr), 0.);
+        assert_eq!(cross_script_distance_with("한글", "hangul", mr), 0.);
+        assert_eq!(cross_script_distance_with("독립", "tongnip", mr), 0.);
+        assert!(cross_script_distance_with("부산", "busan", mr) > 0.);
+        assert_eq!(cross_script_distance_with("", "", mr), 0.);
+        assert_eq!(
+            cross_script_distance_with("서울", "seoul", RomanizationScheme::Revised),
+            cross_script_distance("서울", "seoul")
+        );
+    }
+
+    #[test]
+    fn test_cross_script_distance() {
+        assert_eq!(cross_script_distance("서울", "seoul"), 0.);
+        assert_eq!(cross_script_distance("부산", "busan"), 0.);
+        assert_eq!(cross_script_distance("부산", "pusan"), 0.);
+        assert_eq!(cross_script_distance("대구", "taegu"), 0.);
+        assert_eq!(cross_script_distance("김치", "kimchi"), 0.);
+        assert_eq!(cross_script_distance("제주도", "Cheju-do"), 1. / 8.);
+        // "hankuk" only matches with every ㄱ devoiced
+        assert_eq!(cross_script_distance("한국", "hankuk"), 0.);
+        assert_eq!(cross_script_distance("한국", "hanguk"), 0.);
+        assert_eq!(
+            cross_script_distance("한국", "hangook"),
+            1. - (1. - 2. / 7.)
+        );
+        assert!(cross_script_distance("서울", "busan") > 0.5);
+        assert_eq!(cross_script_distance("", ""), 0.);
+        assert_eq!(cross_script_distance("서울", ""), 1.);
+    }
+}
@@ -0,0 +1,1023 @@
+//! Comparing words against lots of other words, like looking up the closest candidates to a
+//! query, or finding where a pattern shows up in a longer text.
+
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{k_edit_distance_raw, levenshtein_distance, levenshtein_within};
+
+/// The candidate with the smallest Levenshtein distance to `query` along with that distance, or
+/// `None` if there are no candidates. If several are equally close the first one wins.
+///
+/// Each candidate only has to beat the best so far, so most of them are thrown out early by
+/// `levenshtein_within` instead of computing their full distance.
+///
+/// # Examples
+///
+/// ```
+/// let words = ["apple", "banana", "cherry"];
+/// assert_eq!(k_edit_distance::closest("banan", &words), Some(("banana", 1)));
+/// assert_eq!(k_edit_distance::closest("banan", &[]), None);
+/// ```
+pub fn closest<'a>(query: &str, candidates: &'a [&'a str]) -> Option<(&'a str, usize)> {
+    let (first, rest) = candidates.split_first()?;
+    let mut best = (*first, levenshtein_distance(query, first));
+
+    for candidate in rest {
+        if best.1 == 0 {
+            break;
+        }
+        if let Some(dist) = levenshtein_within(query, candidate, best.1 - 1) {
+            best = (*candidate, dist);
+        }
+    }
+
+    Some(best)
+}
+
+/// Up to `k` of the candidates closest to `query` by Levenshtein distance, closest first.
+/// Candidates that are equally close stay in the order they were given.
+///
+/// Only the best `k` seen so far are kept, so the list is never fully sorted and once there are
+/// `k` of them the rest are pruned with `levenshtein_within` like `closest`.
+///
+/// # Examples
+///
+/// ```
+/// let words = ["apple", "apply", "ample", "maple"];
+/// assert_eq!(
+///     k_edit_distance::k_nearest("appel", &words, 2),
+///     vec![("apple", 2), ("apply", 2)]
+/// );
+/// ```
+pub fn k_nearest<'a>(query: &str, candidates: &'a [&'a str], k: usize) -> Vec<(&'a str, usize)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // Max heap on (distance, index) so the worst of the best k is always on top
+    let mut best: BinaryHeap<(usize, usize)> = BinaryHeap::with_capacity(k + 1);
+    for (index, candidate) in candidates.iter().enumerate() {
+        if best.len() < k {
+            best.push((levenshtein_distance(query, candidate), index));
+            continue;
+        }
+
+        let worst = best.peek().unwrap().0;
+        if worst == 0 {
+            break;
+        }
+        if let Some(dist) = levenshtein_within(query, candidate, worst - 1) {
+            best.pop();
+            best.push((dist, index));
+        }
+    }
+
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|(dist, index)| (candidates[index], dist))
+        .collect()
+}
+
+/// A BK-tree of words for finding everything within some Levenshtein distance of a query without
+/// comparing against every word.
+///
+/// Each child is stored under its distance to its parent, and since Levenshtein distance obeys
+/// the triangle inequality only children whose edge is within `max_dist` of the query's distance
+/// to the parent can have a match under them.
+///
+/// # Examples
+///
+/// ```
+/// let mut tree = k_edit_distance::BkTree::new();
+/// for word in ["book", "books", "cake", "boo", "cape"] {
+///     tree.insert(word.to_string());
+/// }
+/// assert_eq!(
+///     tree.search("bok", 1),
+///     vec![("boo".to_string(), 1), ("book".to_string(), 1)]
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BkTree {
+    root: Option<BkNode>,
+}
+
+#[derive(Debug, Clone)]
+struct BkNode {
+    word: String,
+    children: Vec<(usize, BkNode)>,
+}
+
+impl BkTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `word` to the tree, words that are already in it are ignored.
+    pub fn insert(&mut self, word: String) {
+        let Some(mut node) = self.root.as_mut() else {
+            self.root = Some(BkNode {
+                word,
+                children: vec![],
+            });
+            return;
+        };
+
+        loop {
+            let dist = levenshtein_distance(&node.word, &word);
+            if dist == 0 {
+                return;
+            }
+
+            match node.children.iter().position(|(edge, _)| *edge == dist) {
+                Some(index) => node = &mut node.children[index].1,
+                None => {
+                    node.children.push((
+                        dist,
+                        BkNode {
+                            word,
+                            children: vec![],
+                        },
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Every word within `max_dist` of `query` with its distance, closest first and then in
+    /// alphabetical order.
+    pub fn search(&self, query: &str, max_dist: usize) -> Vec<(String, usize)> {
+        let mut found = vec![];
+        let mut stack: Vec<&BkNode> = self.root.iter().collect();
+
+        while let Some(node) = stack.pop() {
+            let dist = levenshtein_distance(query, &node.word);
+            if dist <= max_dist {
+                found.push((node.word.clone(), dist));
+            }
+
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(edge, _)| edge.abs_diff(dist) <= max_dist)
+                    .map(|(_, child)| child),
+            );
+        }
+
+        found.sort_by(|(a_word, a_dist), (b_word, b_dist)| {
+            a_dist.cmp(b_dist).then_with(|| a_word.cmp(b_word))
+        });
+        found
+    }
+}
+
+/// Spelling suggestions from a dictionary, the closest words to a typo with more common words
+/// first when several are just as close. Words are looked up through a `BkTree`.
+///
+/// # Examples
+///
+/// ```
+/// let corrector =
+///     k_edit_distance::Corrector::with_frequencies(&[("the", 500), ("then", 40), ("them", 90)]);
+/// assert_eq!(corrector.suggest("thm", 1, 3), vec!["the", "them"]);
+/// assert_eq!(corrector.suggest("thex", 1, 3), vec!["the", "them", "then"]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Corrector {
+    tree: BkTree,
+    frequencies: BTreeMap<String, usize>,
+}
+
+impl Corrector {
+    /// A corrector where every word is as common as every other, so ties stay alphabetical
+    pub fn new(words: &[&str]) -> Self {
+        let words: Vec<(&str, usize)> = words.iter().map(|word| (*word, 1)).collect();
+        Self::with_frequencies(&words)
+    }
+
+    /// A corrector with how often each word shows up, frequencies of repeated words add up
+    pub fn with_frequencies(words: &[(&str, usize)]) -> Self {
+        let mut corrector = Self::default();
+        for (word, frequency) in words {
+            match corrector.frequencies.get_mut(*word) {
+                Some(total) => *total += frequency,
+                None => {
+                    corrector
+                        .frequencies
+                        .insert(String::from(*word), *frequency);
+                    corrector.tree.insert(String::from(*word));
+                }
+            }
+        }
+        corrector
+    }
+
+    /// Up to `limit` dictionary words within `max_dist` of `word`, the closest first, then the
+    /// most frequent and then in alphabetical order.
+    pub fn suggest(&self, word: &str, max_dist: usize, limit: usize) -> Vec<String> {
+        let mut found = self.tree.search(word, max_dist);
+        // search already sorted them alphabetically within each distance and the sort is stable
+        found.sort_by(|(a_word, a_dist), (b_word, b_dist)| {
+            a_dist
+                .cmp(b_dist)
+                .then_with(|| self.frequencies[b_word].cmp(&self.frequencies[a_word]))
+        });
+        found
+            .into_iter()
+            .take(limit)
+            .map(|(word, _)| word)
+            .collect()
+    }
+}
+
+/// A symmetric delete spelling index, the idea behind SymSpell. Every word is stored under each
+/// string you can get by deleting up to `max_edit` of its characters, so a lookup only has to
+/// generate the deletes of the query and check the words filed under them.
+///
+/// That makes building and memory use grow quickly with `max_edit` but lookups very fast, it's
+/// meant for `max_edit` of 1 or 2 over large dictionaries.
+///
+/// # Examples
+///
+/// ```
+/// let index = k_edit_distance::SymSpell::build(&["book", "boo", "cake", "books"], 2);
+/// assert_eq!(
+///     index.lookup("bok", 1),
+///     vec![("boo".to_string(), 1), ("book".to_string(), 1)]
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SymSpell {
+    words: Vec<String>,
+    deletes: BTreeMap<String, Vec<usize>>,
+    max_edit: usize,
+}
+
+impl SymSpell {
+    /// Indexes `dictionary` for lookups up to `max_edit` away, duplicate words are only kept
+    /// once.
+    pub fn build(dictionary: &[&str], max_edit: usize) -> Self {
+        let mut index = Self {
+            max_edit,
+            ..Self::default()
+        };
+
+        let unique: BTreeSet<&str> = dictionary.iter().copied().collect();
+        for word in unique {
+            let word_index = index.words.len();
+            index.words.push(String::from(word));
+            for delete in deletes(word, max_edit) {
+                index.deletes.entry(delete).or_default().push(word_index);
+            }
+        }
+
+        index
+    }
+
+    /// Every word within `max_edit` of `term` with its distance, closest first and then in
+    /// alphabetical order. `max_edit` is capped at the one the index was built with.
+    pub fn lookup(&self, term: &str, max_edit: usize) -> Vec<(String, usize)> {
+        let max_edit = max_edit.min(self.max_edit);
+
+        let candidates: BTreeSet<usize> = deletes(term, max_edit)
+            .iter()
+            .filter_map(|delete| self.deletes.get(delete))
+            .flatten()
+            .copied()
+            .collect();
+
+        let mut found: Vec<_> = candidates
+            .into_iter()
+            .map(|index| &self.words[index])
+            .map(|word| (word.clone(), levenshtein_distance(term, word)))
+            .filter(|(_, dist)| *dist <= max_edit)
+            .collect();
+        found.sort_by(|(a_word, a_dist), (b_word, b_dist)| {
+            a_dist.cmp(b_dist).then_with(|| a_word.cmp(b_word))
+        });
+        found
+    }
+}
+
+/// `word` and every string made by deleting up to `max_edit` of its characters
+fn deletes(word: &str, max_edit: usize) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    found.insert(String::from(word));
+
+    let mut frontier = vec![word.chars().collect::<Vec<char>>()];
+    for _ in 0..max_edit {
+        let mut next = vec![];
+        for chars in &frontier {
+            for skip in 0..chars.len() {
+                let delete: Vec<char> = chars
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != skip)
+                    .map(|(_, c)| *c)
+                    .collect();
+                if found.insert(delete.iter().collect()) {
+                    next.push(delete);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    found
+}
+
+/// The candidates whose `k_edit_distance` to `query` is at most `max_score`, closest first.
+/// Candidates with the same score stay in the order they were given.
+///
+/// Scores are compared as the exact fractions from `k_edit_distance_raw` when sorting, so two
+/// candidates that are the same distance away always tie even if their floats differ slightly.
+///
+/// # Examples
+///
+/// ```
+/// let words = ["숙어", "하늘", "국어"];
+/// assert_eq!(
+///     k_edit_distance::k_edit_distance_matches("국어", &words, 0.2),
+///     vec![("국어", 0.0), ("숙어", 0.16666667)]
+/// );
+/// ```
+pub fn k_edit_distance_matches<'a>(
+    query: &str,
+    candidates: &'a [&'a str],
+    max_score: f32,
+) -> Vec<(&'a str, f32)> {
+    let mut matches: Vec<_> = candidates
+        .iter()
+        .map(|candidate| {
+            let (edit_distance, max) = k_edit_distance_raw(query, candidate);
+            // Two empty strings are exactly the same
+            (*candidate, edit_distance, max.max(1))
+        })
+        .filter(|(_, edit_distance, max)| *edit_distance as f32 / *max as f32 <= max_score)
+        .collect();
+
+    matches
+        .sort_by(|(_, a_dist, a_max), (_, b_dist, b_max)| (a_dist * b_max).cmp(&(b_dist * a_max)));
+
+    matches
+        .into_iter()
+        .map(|(candidate, edit_distance, max)| (candidate, edit_distance as f32 / max as f32))
+        .collect()
+}
+
+/// A Levenshtein automaton, it answers whether a candidate is within `k` edits of a fixed
+/// pattern in a single pass over the candidate, giving up as soon as it can't be.
+///
+/// This is the nondeterministic automaton where each state is a `(position, errors)` pair, how
+/// much of the pattern has been matched and how many edits that took. Only the fewest errors
+/// for each position are kept since anything reachable from a state with more errors is also
+/// reachable from the one with fewer.
+///
+/// # Examples
+///
+/// ```
+/// let automaton = k_edit_distance::LevAutomaton::new("kitten", 2);
+/// assert!(automaton.matches("sitten"));
+/// assert!(automaton.matches("kitn"));
+/// assert!(!automaton.matches("sitting"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LevAutomaton {
+    pattern: Vec<char>,
+    k: usize,
+}
+
+impl LevAutomaton {
+    pub fn new(pattern: &str, k: usize) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+            k,
+        }
+    }
+
+    /// Whether `levenshtein_distance(pattern, candidate) <= k`
+    pub fn matches(&self, candidate: &str) -> bool {
+        let mut states = self.deletion_closure(vec![(0, 0)]);
+
+        for c in candidate.chars() {
+            let mut next = vec![];
+            for &(position, errors) in &states {
+                if self.pattern.get(position) == Some(&c) {
+                    next.push((position + 1, errors));
+                }
+                if errors < self.k {
+                    // c was inserted
+                    next.push((position, errors + 1));
+                    // c replaced the next pattern character
+                    if position < self.pattern.len() {
+                        next.push((position + 1, errors + 1));
+                    }
+                }
+            }
+
+            states = self.deletion_closure(next);
+            if states.is_empty() {
+                return false;
+            }
+        }
+
+        states
+            .iter()
+            .any(|&(position, _)| position == self.pattern.len())
+    }
+
+    /// Adds the states reachable by deleting pattern characters and drops any state with more
+    /// errors than another at the same position
+    fn deletion_closure(&self, states: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        let mut fewest = vec![usize::MAX; self.pattern.len() + 1];
+        for (position, errors) in states {
+            fewest[position] = fewest[position].min(errors);
+        }
+        for position in 1..fewest.len() {
+            fewest[position] = fewest[position].min(fewest[position - 1].saturating_add(1));
+        }
+
+        fewest
+            .into_iter()
+            .enumerate()
+            .filter(|(_, errors)| *errors <= self.k)
+            .collect()
+    }
+}
+
+/// The Levenshtein distance between every pair of `words`, `matrix[i][j]` is the distance
+/// between `words[i]` and `words[j]`. Only the upper triangle is computed since the distance is
+/// symmetric. With the `rayon` feature the rows are computed in parallel.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(
+///     k_edit_distance::distance_matrix(&["book", "back", "boo"]),
+///     vec![vec![0, 2, 1], vec![2, 0, 3], vec![1, 3, 0]]
+/// );
+/// ```
+pub fn distance_matrix(words: &[&str]) -> Vec<Vec<usize>> {
+    #[cfg(feature = "rayon")]
+    let upper: Vec<Vec<usize>> = (0..words.len())
+        .into_par_iter()
+        .map(|i| upper_row(words, i))
+        .collect();
+    #[cfg(not(feature = "rayon"))]
+    let upper: Vec<Vec<usize>> = (0..words.len()).map(|i| upper_row(words, i)).collect();
+
+    mirror_upper(upper)
+}
+
+/// Distances from `words[i]` to every word after it
+fn upper_row(words: &[&str], i: usize) -> Vec<usize> {
+    words[i + 1..]
+        .iter()
+        .map(|word| levenshtein_distance(words[i], word))
+        .collect()
+}
+
+fn mirror_upper(upper: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    let n = upper.len();
+    let mut matrix = vec![vec![0; n]; n];
+    for (i, row) in upper.into_iter().enumerate() {
+        for (offset, dist) in row.into_iter().enumerate() {
+            let j = i + 1 + offset;
+            matrix[i][j] = dist;
+            matrix[j][i] = dist;
+        }
+    }
+    matrix
+}
+
+/// The window of `text` closest to `pattern` by Levenshtein distance, as
+/// `(start, end, distance)` where `start..end` are `char` indices into `text`. If several windows
+/// are as close the one that ends first is picked.
+///
+/// This is the usual edit distance DP except the first row is all zeros, so the pattern can
+/// start anywhere in the text for free.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(
+///     k_edit_distance::best_substring_match("worl", "hello world"),
+///     (6, 10, 0)
+/// );
+/// ```
+pub fn best_substring_match(pattern: &str, text: &str) -> (usize, usize, usize) {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (end, (distance, start)) = substring_row(&pattern, &text)
+        .into_iter()
+        .enumerate()
+        .min_by_key(|(_, (distance, _))| *distance)
+        .unwrap();
+    (start, end, distance)
+}
+
+/// Every fuzzy occurrence of `pattern` in `text` that's within `k` edits, as
+/// `(start, end, distance)` with `char` indices like `best_substring_match`, in the order they
+/// are in `text`. Empty windows, which any `k` at least as long as the pattern would allow,
+/// aren't matches.
+///
+/// Each position in `text` can end a window, and the windows ending around a real match
+/// overlap each other, so only the best of them is kept. The closest window is taken first,
+/// then the closest that doesn't overlap it and so on, so the matches never overlap. When
+/// windows are as close the longer one wins so "colo" doesn't beat "colour" for "color", and
+/// then the one ending first. Windows that only touch, one ending where the next
+/// starts, don't count as overlapping.
+///
+/// # Examples
+///
+/// ```
+/// let text = "the colour of the collar";
+/// assert_eq!(
+///     k_edit_distance::find_all_within("color", text, 2),
+///     vec![(4, 10, 1), (18, 24, 2)]
+/// );
+/// ```
+pub fn find_all_within(pattern: &str, text: &str, k: usize) -> Vec<(usize, usize, usize)> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let mut candidates: Vec<(usize, usize, usize)> = substring_row(&pattern, &text)
+        .into_iter()
+        .enumerate()
+        .filter(|&(end, (distance, start))| distance <= k && start < end)
+        .map(|(end, (distance, start))| (start, end, distance))
+        .collect();
+    candidates.sort_by_key(|&(start, end, distance)| (distance, Reverse(end - start), end));
+
+    let mut matches: Vec<(usize, usize, usize)> = vec![];
+    for candidate in candidates {
+        let (start, end, _) = candidate;
+        if matches.iter().all(|&(s, e, _)| end <= s || e <= start) {
+            matches.push(candidate);
+        }
+    }
+    matches.sort_unstable();
+    matches
+}
+
+/// The last row of the edit distance DP between `pattern` and windows of `text`, where the
+/// first row is all zeros so the pattern can start anywhere in the text for free. Entry `j` is
+/// the distance of the closest window ending at `j` and where that window starts.
+fn substring_row(pattern: &[char], text: &[char]) -> Vec<(usize, usize)> {
+    let n = text.len();
+
+    // Each cell also remembers which column of text its alignment started at
+    let mut prev: Vec<(usize, usize)> = (0..=n).map(|j| (0, j)).collect();
+    let mut curr = vec![(0, 0); n + 1];
+
+    for (i, p_char) in pattern.iter().enumerate() {
+        curr[0] = (i + 1, 0);
+        for j in 1..=n {
+            let substitution_cost = if *p_char == text[j - 1] { 0 } else { 1 };
+
+            let diagonal = (prev[j - 1].0 + substitution_cost, prev[j - 1].1);
+            let up = (prev[j].0 + 1, prev[j].1);
+            let left = (curr[j - 1].0 + 1, curr[j - 1].1);
+            curr[j] =
+                [up, left].into_iter().fold(
+                    diagonal,
+                    |best, cell| if cell.0 < best.0 { cell } else { best },
+                );
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev
+}
+
+const FUZZY_MATCH: f32 = 1.0;
+const FUZZY_BONUS: f32 = 0.5;
+const FUZZY_GAP: f32 = 0.1;
+
+/// How well `query` matches `candidate` the way fuzzy finders like fzf filter a list as you
+/// type, or `None` if the characters of `query` don't all show up in `candidate` in order.
+/// Higher is better and the scores are only meant for ranking candidates for the same query.
+/// Letters are compared ignoring case.
+///
+/// Each character of `query` scores 1.0 wherever it's matched, plus 0.5 if it's right after
+/// the character matched before it or at the start of a word (the start of `candidate`, after
+/// anything that isn't a letter or number, or a capital after a lower case letter like in
+/// `camelCase`). Every character skipped between two matches takes off 0.1. The best way of
+/// matching is the one that counts, so "ke" matches the "e" of "edit" in "knife edit".
+///
+/// This isn't an edit distance, a candidate with the query spelled out in it scores well however
+/// much else it has.
+///
+/// # Examples
+///
+/// ```
+/// use k_edit_distance::fuzzy_match_score;
+///
+/// assert!(fuzzy_match_score("ke", "kedit") > fuzzy_match_score("ke", "knife edit"));
+/// assert_eq!(fuzzy_match_score("ke", "edit"), None);
+/// ```
+pub fn fuzzy_match_score(query: &str, candidate: &str) -> Option<f32> {
+    let query: Vec<char> = query.chars().collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    let n = candidate.len();
+    if query.is_empty() {
+        return Some(0.);
+    }
+
+    let boundary: Vec<bool> = (0..n)
+        .map(|j| {
+            let Some(before) = j.checked_sub(1).map(|j| candidate[j]) else {
+                return true;
+            };
+            !before.is_alphanumeric() || (before.is_lowercase() && candidate[j].is_uppercase())
+        })
+        .collect();
+    let bonus = |j: usize| if boundary[j] { FUZZY_BONUS } else { 0. };
+
+    // The best score with the query so far matched and its last character at `j`
+    let mut prev: Vec<f32> = (0..n)
+        .map(|j| {
+            if same_letter(query[0], candidate[j]) {
+                FUZZY_MATCH + bonus(j)
+            } else {
+                f32::NEG_INFINITY
+            }
+        })
+        .collect();
+    let mut curr = vec![f32::NEG_INFINITY; n];
+
+    for q_char in &query[1..] {
+        // The best of `prev` ending two or more back, less the characters skipped since
+        let mut after_gap = f32::NEG_INFINITY;
+        for j in 0..n {
+            curr[j] = f32::NEG_INFINITY;
+            if j >= 2 {
+                after_gap = after_gap.max(prev[j - 2]) - FUZZY_GAP;
+            }
+            if j == 0 || !same_letter(*q_char, candidate[j]) {
+                continue;
+            }
+            let consecutive = prev[j - 1] + FUZZY_MATCH + FUZZY_BONUS;
+            curr[j] = consecutive.max(after_gap + FUZZY_MATCH + bonus(j));
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev.into_iter().fold(None, |best: Option<f32>, score| {
+        if score == f32::NEG_INFINITY {
+            best
+        } else {
+            Some(best.map_or(score, |best| best.max(score)))
+        }
+    })
+}
+
+fn same_letter(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{random_string, xorshift};
+    use alloc::string::ToString;
+
+    const WORDS: &[&str] = &["hello", "help", "yellow", "hollow", "world", "held"];
+
+    #[test]
+    fn test_closest() {
+        assert_eq!(closest("helo", WORDS), Some(("hello", 1)));
+        assert_eq!(closest("wrld", WORDS), Some(("world", 1)));
+        assert_eq!(closest("hello", WORDS), Some(("hello", 0)));
+        assert_eq!(closest("xyzzy", WORDS), Some(("hello", 5)));
+        // "help" and "held" are both 1 away, the first one wins
+        assert_eq!(closest("hel", WORDS), Some(("help", 1)));
+        assert_eq!(closest("hel", &["held", "help"]), Some(("held", 1)));
+        assert_eq!(closest("hello", &[]), None);
+        assert_eq!(
+            closest("국어", &["숙어", "국어", "신문"]),
+            Some(("국어", 0))
+        );
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        assert_eq!(
+            k_nearest("helo", WORDS, 3),
+            vec![("hello", 1), ("help", 1), ("held", 1)]
+        );
+        assert_eq!(
+            k_nearest("hollow", WORDS, 2),
+            vec![("hollow", 0), ("hello", 2)]
+        );
+        // Ties keep the input order
+        assert_eq!(
+            k_nearest("hel", &["held", "hello", "help"], 2),
+            vec![("held", 1), ("help", 1)]
+        );
+        assert_eq!(k_nearest("helo", WORDS, 0), vec![]);
+        assert_eq!(k_nearest("helo", &[], 3), vec![]);
+
+        // Asking for more than there are gives everything, sorted
+        let mut expected: Vec<_> = WORDS
+            .iter()
+            .map(|word| (*word, levenshtein_distance("helo", word)))
+            .collect();
+        expected.sort_by_key(|(_, dist)| *dist);
+        assert_eq!(k_nearest("helo", WORDS, 100), expected);
+    }
+
+    #[test]
+    fn test_bk_tree() {
+        let dictionary = [
+            "hello",
+            "help",
+            "yellow",
+            "hollow",
+            "world",
+            "held",
+            "hell",
+            "shell",
+            "word",
+            "sword",
+            "국어",
+            "숙어",
+            "한국어",
+            "hello",
+        ];
+        let mut tree = BkTree::new();
+        for word in dictionary {
+            tree.insert(word.to_string());
+        }
+
+        assert_eq!(tree.search("hello", 0), vec![("hello".to_string(), 0)]);
+        assert_eq!(tree.search("zzzzzzzz", 2), vec![]);
+        assert_eq!(BkTree::new().search("hello", 3), vec![]);
+
+        for query in ["helo", "wrd", "hollow", "국", "shelf", ""] {
+            for max_dist in 0..=4 {
+                let mut expected: Vec<_> = dictionary
+                    .iter()
+                    .map(|word| (word.to_string(), levenshtein_distance(query, word)))
+                    .filter(|(_, dist)| *dist <= max_dist)
+                    .collect();
+                expected.sort_by(|(a_word, a_dist), (b_word, b_dist)| {
+                    a_dist.cmp(b_dist).then_with(|| a_word.cmp(b_word))
+                });
+                expected.dedup();
+                assert_eq!(tree.search(query, max_dist), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_corrector() {
+        let corrector = Corrector::with_frequencies(&[
+            ("hello", 10),
+            ("help", 50),
+            ("held", 5),
+            ("hell", 20),
+            ("yellow", 3),
+            ("world", 40),
+            ("hello", 45),
+            ("국어", 7),
+            ("숙어", 2),
+        ]);
+
+        // all one away, the most frequent wins
+        assert_eq!(
+            corrector.suggest("helo", 1, 10),
+            vec!["hello", "help", "hell", "held"]
+        );
+        assert_eq!(
+            corrector.suggest("hel", 1, 10),
+            vec!["help", "hell", "held"]
+        );
+        // closer always beats more frequent
+        assert_eq!(
+            corrector.suggest("held", 1, 10),
+            vec!["held", "help", "hell"]
+        );
+        assert_eq!(corrector.suggest("hel", 1, 2), vec!["help", "hell"]);
+        assert_eq!(corrector.suggest("hel", 1, 0), Vec::<String>::new());
+        assert_eq!(corrector.suggest("쿡어", 1, 10), vec!["국어", "숙어"]);
+        assert_eq!(corrector.suggest("zzz", 1, 10), Vec::<String>::new());
+
+        // without frequencies ties are alphabetical
+        let corrector = Corrector::new(&["help", "held", "hell"]);
+        assert_eq!(
+            corrector.suggest("hel", 1, 10),
+            vec!["held", "hell", "help"]
+        );
+    }
+
+    #[test]
+    fn test_sym_spell() {
+        let dictionary = [
+            "hello",
+            "help",
+            "yellow",
+            "hollow",
+            "world",
+            "held",
+            "hell",
+            "shell",
+            "word",
+            "sword",
+            "국어",
+            "숙어",
+            "한국어",
+            "hello",
+            "a",
+            "",
+        ];
+        let index = SymSpell::build(&dictionary, 2);
+
+        for query in ["helo", "wrd", "hollow", "국", "shelf", "", "xyzzy", "hwllo"] {
+            for max_edit in 0..=2 {
+                let mut expected: Vec<_> = dictionary
+                    .iter()
+                    .map(|word| (word.to_string(), levenshtein_distance(query, word)))
+                    .filter(|(_, dist)| *dist <= max_edit)
+                    .collect();
+                expected.sort_by(|(a_word, a_dist), (b_word, b_dist)| {
+                    a_dist.cmp(b_dist).then_with(|| a_word.cmp(b_word))
+                });
+                expected.dedup();
+                assert_eq!(index.lookup(query, max_edit), expected);
+            }
+        }
+
+        // capped at what the index was built for
+        let index = SymSpell::build(&dictionary, 1);
+        assert_eq!(index.lookup("wrd", 2), vec![("word".to_string(), 1)]);
+        assert_eq!(SymSpell::build(&[], 2).lookup("hello", 2), vec![]);
+    }
+
+    #[test]
+    fn test_distance_matrix() {
+        let matrix = distance_matrix(WORDS);
+        for (i, s) in WORDS.iter().enumerate() {
+            for (j, t) in WORDS.iter().enumerate() {
+                assert_eq!(matrix[i][j], levenshtein_distance(s, t));
+            }
+        }
+        assert_eq!(distance_matrix(&[]), Vec::<Vec<usize>>::new());
+        assert_eq!(distance_matrix(&["a"]), vec![vec![0]]);
+
+        // Whichever way distance_matrix was built it should match doing it one row at a time
+        let sequential = mirror_upper((0..WORDS.len()).map(|i| upper_row(WORDS, i)).collect());
+        assert_eq!(matrix, sequential);
+    }
+
+    #[test]
+    fn test_best_substring_match() {
+        assert_eq!(best_substring_match("worl", "hello world"), (6, 10, 0));
+        assert_eq!(best_substring_match("hello", "hello world"), (0, 5, 0));
+        assert_eq!(best_substring_match("world", "hello world"), (6, 11, 0));
+        // Off by one character, "orld" with a substitution is as close as "world" with a
+        // deletion and the diagonal is preferred
+        assert_eq!(best_substring_match("wrld", "hello world"), (7, 11, 1));
+        assert_eq!(best_substring_match("wxrld", "hello world"), (6, 11, 1));
+        assert_eq!(best_substring_match("국어", "저는 숙어 사전을"), (3, 5, 1));
+        // First window to end wins
+        assert_eq!(best_substring_match("ab", "xxabxxab"), (2, 4, 0));
+        assert_eq!(best_substring_match("", "hello"), (0, 0, 0));
+        assert_eq!(best_substring_match("abc", ""), (0, 0, 3));
+
+        let text = "the quick brown fox jumps over the lazy dog";
+        for pattern in ["quack", "brwn fx", "lazy cat", "jumped"] {
+            let (start, end, distance) = best_substring_match(pattern, text);
+            let window: alloc::string::String =
+                text.chars().skip(start).take(end - start).collect();
+            assert_eq!(levenshtein_distance(pattern, &window), distance);
+        }
+    }
+
+    #[test]
+    fn test_k_edit_distance_matches() {
+        let words = [
+            "숙어",
+            "국어",
+            "나무가지",
+            "신문",
+            "검은색",
+            "분홍색",
+            "진공청소기",
+            "택시",
+            "하늘",
+        ];
+        assert_eq!(
+            k_edit_distance_matches("국어", &words, 0.2),
+            vec![("국어", 0.), ("숙어", 0.16666667)]
+        );
+        assert_eq!(
+            k_edit_distance_matches("나뭇가지", &words, 0.1),
+            vec![("나무가지", 0.083333336)]
+        );
+        assert_eq!(
+            k_edit_distance_matches("식물", &words, 0.4),
+            vec![("신문", 0.33333334)]
+        );
+        // Unrelated words are left out
+        assert_eq!(
+            k_edit_distance_matches("하늘", &words, 0.5),
+            vec![("하늘", 0.)]
+        );
+        assert_eq!(k_edit_distance_matches("하늘", &words, -1.), vec![]);
+        assert_eq!(k_edit_distance_matches("국어", &[], 1.), vec![]);
+        // Everything comes back in order with a big enough threshold
+        let all = k_edit_distance_matches("국어", &words, 1.);
+        assert_eq!(all.len(), words.len());
+        assert!(all.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_lev_automaton() {
+        let mut state: u32 = 0x1234_5678;
+        let alphabet = ['a', 'b', 'c', '국'];
+        let mut random_word = |max_len: u32| {
+            let len = xorshift(&mut state) % max_len;
+            random_string(&mut state, len as usize, &alphabet)
+        };
+
+        for _ in 0..50 {
+            let pattern = random_word(8);
+            let candidates: Vec<String> = (0..40).map(|_| random_word(10)).collect();
+            for k in 0..=2 {
+                let automaton = LevAutomaton::new(&pattern, k);
+                for candidate in &candidates {
+                    assert_eq!(
+                        automaton.matches(candidate),
+                        levenshtein_distance(&pattern, candidate) <= k,
+                        "{pattern} {candidate} {k}"
+                    );
+                }
+            }
+        }
+
+        assert!(LevAutomaton::new("", 0).matches(""));
+        assert!(!LevAutomaton::new("", 0).matches("a"));
+        assert!(LevAutomaton::new("ab", 2).matches(""));
+        assert!(!LevAutomaton::new("abc", 2).matches(""));
+    }
+
+    #[test]
+    fn test_find_all_within() {
+        let text = "the colour of the collar";
+        assert_eq!(
+            find_all_within("color", text, 2),
+            vec![(4, 10, 1), (18, 24, 2)]
+        );
+        // Too strict for the second one
+        assert_eq!(find_all_within("color", text, 1), vec![(4, 10, 1)]);
+        assert_eq!(find_all_within("color", text, 0), vec![]);
+
+        // Exact matches next to each other don't overlap
+        assert_eq!(find_all_within("ab", "abab", 0), vec![(0, 2, 0), (2, 4, 0)]);
+        // The closer window wins when they overlap
+        assert_eq!(find_all_within("abc", "xabcx", 1), vec![(1, 4, 0)]);
+        assert_eq!(
+            find_all_within("국어", "국어 사전과 국아", 1),
+            vec![(0, 2, 0), (7, 9, 1)]
+        );
+
+        assert_eq!(find_all_within("abc", "", 5), vec![]);
+        assert_eq!(find_all_within("", "abc", 5), vec![]);
+    }
+
+    #[test]
+    fn test_fuzzy_match_score() {
+        assert!(fuzzy_match_score("ke", "kedit") > fuzzy_match_score("ke", "knife edit"));
+        // k, then the e starting "edit" after 5 skipped
+        assert_eq!(fuzzy_match_score("ke", "kedit"), Some(3.));
+        assert_eq!(
+            fuzzy_match_score("ke", "knife edit"),
+            Some(1.5 + 1.5 - 5. * 0.1)
+        );
+        // Start of word bonuses
+        assert!(fuzzy_match_score("fb", "foo_bar") > fuzzy_match_score("fb", "fabric"));
+        assert!(fuzzy_match_score("fb", "fooBar") > fuzzy_match_score("fb", "foobar"));
+        assert!(fuzzy_match_score("edit", "k_edit") > fuzzy_match_score("edit", "kedit"));
+
+        assert_eq!(
+            fuzzy_match_score("KE", "kedit"),
+            fuzzy_match_score("ke", "kedit")
+        );
+        assert_eq!(fuzzy_match_score("국어", "한국 어"), Some(1. + 1.5 - 0.1));
+        assert_eq!(fuzzy_match_score("ek", "kedit"), None);
+        assert_eq!(fuzzy_match_score("kedits", "kedit"), None);
+        assert_eq!(fuzzy_match_score("a", ""), None);
+        assert_eq!(fuzzy_match_score("", "kedit"), Some(0.));
+        assert_eq!(fuzzy_match_score("", ""), Some(0.));
+    }
+}
@@ -0,0 +1,248 @@
+//! Measures based on the parts two strings have in common, in the same order.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The similarity Python's `difflib.SequenceMatcher.ratio()` gives, `2 * M / T` where `M` is
+/// how many characters are in matching blocks and `T` is the length of both strings together.
+/// 1.0 is exactly the same, 0.0 is nothing in common and two empty strings are 1.0.
+///
+/// The matching blocks come from the Ratcliff/Obershelp algorithm, take the longest common run
+/// and repeat on what's left to either side of it. Unlike difflib no characters are ever
+/// treated as junk, so results for strings of 200 or more characters can differ from difflib's
+/// default `autojunk` behaviour.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::sequence_matcher_ratio("abcd", "bcde"), 0.75);
+/// // swapping two characters loses everything for Levenshtein but not here
+/// assert_eq!(k_edit_distance::sequence_matcher_ratio("ab", "ba"), 0.5);
+/// assert_eq!(k_edit_distance::levenshtein_ratio("ab", "ba"), 0.0);
+/// ```
+pub fn sequence_matcher_ratio(s: impl AsRef<str>, t: impl AsRef<str>) -> f32 {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    let total = s.len() + t.len();
+    if total == 0 {
+        return 1.0;
+    }
+
+    let mut matches = 0;
+    let mut remaining = vec![(0, s.len(), 0, t.len())];
+    while let Some((s_lo, s_hi, t_lo, t_hi)) = remaining.pop() {
+        let (i, j, len) = longest_match(&s[s_lo..s_hi], &t[t_lo..t_hi]);
+        if len == 0 {
+            continue;
+        }
+
+        matches += len;
+        let (i, j) = (s_lo + i, t_lo + j);
+        remaining.push((s_lo, i, t_lo, j));
+        remaining.push((i + len, s_hi, j + len, t_hi));
+    }
+
+    (2 * matches) as f32 / total as f32
+}
+
+/// The longest run that's in both `s` and `t`, as where it starts in each and its length. Ties
+/// go to the run that starts first in `s` and then first in `t`, the same as difflib.
+fn longest_match(s: &[char], t: &[char]) -> (usize, usize, usize) {
+    let mut best = (0, 0, 0);
+    // prev[j + 1] is the length of the run ending at the previous char of s and t[j]
+    let mut prev = vec![0; t.len() + 1];
+    let mut curr = vec![0; t.len() + 1];
+
+    for (i, s_char) in s.iter().enumerate() {
+        for (j, t_char) in t.iter().enumerate() {
+            curr[j + 1] = if s_char == t_char { prev[j] + 1 } else { 0 };
+            if curr[j + 1] > best.2 {
+                best = (i + 1 - curr[j + 1], j + 1 - curr[j + 1], curr[j + 1]);
+            }
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    best
+}
+
+/// The length of the longest common subsequence of `s` and `t`, the most `char`s you can keep
+/// from both in the same order (they don't have to be next to each other).
+///
+/// Only inserting and deleting, with no substitutions, turns `s` into `t` in
+/// `m + n - 2 * lcs_length(s, t)` edits where `m` and `n` are their lengths in `char`s.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::lcs_length("ABCBDAB", "BDCAB"), 4);
+/// ```
+pub fn lcs_length(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let t: Vec<char> = t.chars().collect();
+    let mut prev = vec![0; t.len() + 1];
+    let mut curr = vec![0; t.len() + 1];
+
+    for s_char in s.chars() {
+        for (j, t_char) in t.iter().enumerate() {
+            curr[j + 1] = if s_char == *t_char {
+                prev[j] + 1
+            } else {
+                prev[j + 1].max(curr[j])
+            };
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[t.len()]
+}
+
+/// One longest common subsequence of `s` and `t`, there can be several of the same length and
+/// which one you get isn't specified beyond being `lcs_length` long.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::lcs("kitten", "sitting"), "ittn");
+/// ```
+pub fn lcs(s: impl AsRef<str>, t: impl AsRef<str>) -> String {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+    let (m, n) = (s.len(), t.len());
+
+    // d[i][j] is the LCS length of s[..i] and t[..j]
+    let mut d = vec![vec![0; n + 1]; m + 1];
+    for i in 1..=m {
+        for j in 1..=n {
+            d[i][j] = if s[i - 1] == t[j - 1] {
+                d[i - 1][j - 1] + 1
+            } else {
+                d[i - 1][j].max(d[i][j - 1])
+            };
+        }
+    }
+
+    let mut found = Vec::with_capacity(d[m][n]);
+    let (mut i, mut j) = (m, n);
+    while i > 0 && j > 0 {
+        if s[i - 1] == t[j - 1] {
+            found.push(s[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if d[i - 1][j] >= d[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    found.iter().rev().collect()
+}
+
+/// The longest run of `char`s that shows up in both `s` and `t`, unlike `lcs` it has to be
+/// contiguous in both. If there are several of the same length the one that ends first in `s`
+/// wins, and it's empty when there is nothing in common.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::longest_common_substring("ABABC", "BABCA"), "BABC");
+/// assert_eq!(k_edit_distance::longest_common_substring("abc", "xyz"), "");
+/// ```
+pub fn longest_common_substring(s: impl AsRef<str>, t: impl AsRef<str>) -> String {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    let s: Vec<char> = s.chars().collect();
+    let t: Vec<char> = t.chars().collect();
+
+    // prev[j + 1] is the length of the common suffix of s up to the previous char and t[..=j]
+    let mut prev = vec![0; t.len() + 1];
+    let mut curr = vec![0; t.len() + 1];
+    let mut longest = 0;
+    let mut end = 0;
+
+    for (i, s_char) in s.iter().enumerate() {
+        for (j, t_char) in t.iter().enumerate() {
+            curr[j + 1] = if s_char == t_char { prev[j] + 1 } else { 0 };
+            if curr[j + 1] > longest {
+                longest = curr[j + 1];
+                end = i + 1;
+            }
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    s[end - longest..end].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_matcher_ratio() {
+        // the same as difflib.SequenceMatcher(None, s, t).ratio()
+        assert_eq!(sequence_matcher_ratio("abcd", "bcde"), 0.75);
+        assert_eq!(sequence_matcher_ratio("kitten", "sitting"), 8.0 / 13.0);
+        assert_eq!(
+            sequence_matcher_ratio("GESTALT PATTERN MATCHING", "GESTALT PRACTICE"),
+            0.6
+        );
+        assert_eq!(sequence_matcher_ratio("국어", "숙어"), 0.5);
+        assert_eq!(sequence_matcher_ratio("", ""), 1.0);
+        assert_eq!(sequence_matcher_ratio("abc", ""), 0.0);
+        assert_eq!(sequence_matcher_ratio("abc", "xyz"), 0.0);
+        assert_eq!(sequence_matcher_ratio("same", "same"), 1.0);
+    }
+
+    fn is_subsequence(sub: &str, of: &str) -> bool {
+        let mut of = of.chars();
+        sub.chars().all(|c| of.any(|other| other == c))
+    }
+
+    #[test]
+    fn test_lcs_length() {
+        assert_eq!(lcs_length("ABCBDAB", "BDCAB"), 4);
+        assert_eq!(lcs_length("kitten", "sitting"), 4);
+        assert_eq!(lcs_length("국어", "한국어"), 2);
+        assert_eq!(lcs_length("", "abc"), 0);
+        assert_eq!(lcs_length("abc", "xyz"), 0);
+
+        // insert/delete only distance
+        let (s, t) = ("kitten", "sitting");
+        assert_eq!(s.len() + t.len() - 2 * lcs_length(s, t), 5);
+    }
+
+    #[test]
+    fn test_lcs() {
+        for (s, t) in [
+            ("ABCBDAB", "BDCAB"),
+            ("kitten", "sitting"),
+            ("국어", "한국어"),
+            ("", "abc"),
+            ("abc", "xyz"),
+            ("AGGTAB", "GXTXAYB"),
+        ] {
+            let found = lcs(s, t);
+            assert_eq!(found.chars().count(), lcs_length(s, t));
+            assert!(is_subsequence(&found, s), "{found} {s}");
+            assert!(is_subsequence(&found, t), "{found} {t}");
+        }
+        assert_eq!(lcs("AGGTAB", "GXTXAYB"), "GTAB");
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        assert_eq!(longest_common_substring("ABABC", "BABCA"), "BABC");
+        assert_eq!(longest_common_substring("kitten", "sitting"), "itt");
+        assert_eq!(longest_common_substring("국어사전", "한국어"), "국어");
+        assert_eq!(longest_common_substring("", "abc"), "");
+        assert_eq!(longest_common_substring("abc", ""), "");
+        assert_eq!(longest_common_substring("abc", "xyz"), "");
+        // "ab" and "cd" are both 2 long, "ab" ends first in s
+        assert_eq!(longest_common_substring("abxcd", "cdyab"), "ab");
+    }
+}
@@ -0,0 +1,94 @@
+//! Edit distances over the words of a sentence instead of its characters.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{damerau_levenshtein_slice, levenshtein_slice};
+
+/// How many whole words have to be inserted, deleted or substituted to turn `s` into `t`, with
+/// words split on Unicode whitespace. Words are only equal if they match exactly, punctuation
+/// included.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::token_levenshtein("the cat sat", "the dog sat"), 1);
+/// assert_eq!(k_edit_distance::token_levenshtein("the cat sat", "the  cat\tsat"), 0);
+/// ```
+pub fn token_levenshtein(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_slice(&tokens(s), &tokens(t))
+}
+
+/// Like `token_levenshtein` but words that only differ in case are equal, using the same
+/// lower casing as `levenshtein_distance_ci`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::token_levenshtein_ci("The Cat sat", "the cat SAT"), 0);
+/// ```
+pub fn token_levenshtein_ci(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    levenshtein_slice(&lowercase_tokens(s), &lowercase_tokens(t))
+}
+
+/// Like `token_levenshtein` but swapping two adjacent words only counts as one edit, the
+/// word-level `damerau_levenshtein_distance`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(k_edit_distance::token_damerau("I saw her", "saw I her"), 1);
+/// assert_eq!(k_edit_distance::token_levenshtein("I saw her", "saw I her"), 2);
+/// ```
+pub fn token_damerau(s: impl AsRef<str>, t: impl AsRef<str>) -> usize {
+    let (s, t) = (s.as_ref(), t.as_ref());
+    damerau_levenshtein_slice(&tokens(s), &tokens(t))
+}
+
+fn tokens(s: &str) -> Vec<&str> {
+    s.split_whitespace().collect()
+}
+
+fn lowercase_tokens(s: &str) -> Vec<String> {
+    s.split_whitespace()
+        .map(|token| token.chars().flat_map(char::to_lowercase).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_levenshtein() {
+        assert_eq!(token_levenshtein("the cat sat", "the dog sat"), 1);
+        assert_eq!(token_levenshtein("the cat sat", "the cat sat down"), 1);
+        // reordering isn't free
+        assert_eq!(token_levenshtein("a b", "b a"), 2);
+        assert_eq!(token_levenshtein("", "one two"), 2);
+        assert_eq!(token_levenshtein("  ", ""), 0);
+        assert_eq!(token_levenshtein("국어 공부", "국어\u{3000}공부"), 0);
+        assert_eq!(token_levenshtein("The cat", "the cat"), 1);
+    }
+
+    #[test]
+    fn test_token_levenshtein_ci() {
+        assert_eq!(token_levenshtein_ci("The cat", "the CAT"), 0);
+        assert_eq!(token_levenshtein_ci("A b", "b a"), 2);
+        assert_eq!(token_levenshtein_ci("STRASSE", "straße"), 1);
+    }
+
+    #[test]
+    fn test_token_damerau() {
+        assert_eq!(token_damerau("a b", "b a"), 1);
+        assert_eq!(token_levenshtein("a b", "b a"), 2);
+        assert_eq!(token_damerau("the cat sat", "the dog sat"), 1);
+        assert_eq!(token_damerau("I saw her", "her saw I"), 2);
+        // punctuation stays on the word it's attached to
+        assert_eq!(token_damerau("hello, world", "world hello,"), 1);
+        assert_eq!(token_damerau("hello, world", "world, hello"), 2);
+        assert_eq!(token_damerau("", ""), 0);
+    }
+}
@@ -0,0 +1,74 @@
+//! JavaScript bindings for the most common functions. The crate type is picked when building
+//! instead of in the manifest, so build them with
+//! `cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//! and run `wasm-bindgen --target web` on the `.wasm` it makes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+/// `levenshtein_distance` for JavaScript
+#[wasm_bindgen(js_name = levenshteinDistance)]
+pub fn levenshtein_distance(s: &str, t: &str) -> usize {
+    crate::levenshtein_distance(s, t)
+}
+
+/// `k_edit_distance` for JavaScript
+#[wasm_bindgen(js_name = kEditDistance)]
+pub fn k_edit_distance(s: &str, t: &str) -> f32 {
+    crate::k_edit_distance(s, t)
+}
+
+/// The result of `closest` since JavaScript has no tuples.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosestMatch {
+    word: String,
+    distance: usize,
+}
+
+#[wasm_bindgen]
+impl ClosestMatch {
+    #[wasm_bindgen(getter)]
+    pub fn word(&self) -> String {
+        self.word.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+/// `closest` for JavaScript, `candidates` is an array of strings. Returns `undefined` if there
+/// are no candidates.
+#[wasm_bindgen]
+pub fn closest(query: &str, candidates: Vec<String>) -> Option<ClosestMatch> {
+    let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+    crate::closest(query, &candidates).map(|(word, distance)| ClosestMatch {
+        word: String::from(word),
+        distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn test_wasm() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(
+            k_edit_distance("국어", "숙어"),
+            crate::k_edit_distance("국어", "숙어")
+        );
+
+        let found = closest("helo", vec!["world".to_string(), "hello".to_string()]).unwrap();
+        assert_eq!(found.word(), "hello");
+        assert_eq!(found.distance(), 1);
+        assert_eq!(closest("helo", vec![]), None);
+    }
+}